@@ -0,0 +1,244 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{ HashMap, HashSet };
+use std::hash::{ Hash, Hasher };
+use crate::{ display_grid_animated, Direction, Grid, Position, SparseGrid };
+
+/// Which neighbors a rule gets to see.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Neighborhood {
+    /// All 8 surrounding cells
+    #[default]
+    Moore,
+    /// The 4 orthogonal cells
+    VonNeumann,
+}
+
+impl Neighborhood {
+    pub fn directions(&self) -> Vec<Direction> {
+        match self {
+            Neighborhood::Moore => Direction::all().collect(),
+            Neighborhood::VonNeumann => Direction::cardinals().collect(),
+        }
+    }
+}
+
+/// Why a run stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutomatonOutcome {
+    /// The state after `n` generations is a fixpoint of the rule
+    Stable(usize),
+    /// The state after `start + length` generations equals the state after `start`
+    Cycle {
+        start: usize,
+        length: usize,
+    },
+    /// The step limit was reached without settling
+    StepLimit(usize),
+}
+
+/// Runs a rule synchronously over every cell until the state is stable, a cycle is
+/// found or the step limit is reached.
+///
+/// # Example
+/// ```
+/// use aoc_utils::{ Automaton, AutomatonOutcome, Grid };
+/// let mut grid: Grid = "#..\n...\n..#".parse().unwrap();
+/// // A cell spreads to empty cells touching it, until the grid is full
+/// let outcome = Automaton::new().run_grid(&mut grid, |_, &cell, neighbors| {
+///     if neighbors.contains(&'#') { '#' } else { cell }
+/// });
+/// assert_eq!(outcome, AutomatonOutcome::Stable(2));
+/// assert!(grid.data.iter().all(|&c| c == '#'));
+/// ```
+pub struct Automaton<T> {
+    neighborhood: Neighborhood,
+    max_steps: Option<usize>,
+    detect_cycles: bool,
+    animation: Option<Animation<T>>,
+}
+
+/// Frame delay and the function picking the character drawn for a cell
+type Animation<T> = (u64, Box<dyn Fn(&T) -> Option<char>>);
+
+impl<T> Default for Automaton<T> {
+    fn default() -> Self {
+        Automaton {
+            neighborhood: Neighborhood::Moore,
+            max_steps: None,
+            detect_cycles: false,
+            animation: None,
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + Hash> Automaton<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn neighborhood(mut self, neighborhood: Neighborhood) -> Self {
+        self.neighborhood = neighborhood;
+        self
+    }
+
+    pub fn max_steps(mut self, steps: usize) -> Self {
+        self.max_steps = Some(steps);
+        self
+    }
+
+    /// Keeps every generation to stop on the first repeated state. States are looked
+    /// up by hash and compared in full, so a hash collision can't report a false cycle.
+    pub fn detect_cycles(mut self) -> Self {
+        self.detect_cycles = true;
+        self
+    }
+
+    /// Draws each generation with `display_grid_animated`, `to_char` picking the
+    /// character of a cell or `None` to leave it as background.
+    pub fn animate(
+        mut self,
+        delay_ms: u64,
+        to_char: impl Fn(&T) -> Option<char> + 'static
+    ) -> Self {
+        self.animation = Some((delay_ms, Box::new(to_char)));
+        self
+    }
+
+    /// Steps a dense grid. The rule receives the position, the cell and the values of
+    /// its in-bounds neighbors (following the grid topology) and returns the next value.
+    pub fn run_grid(
+        &self,
+        grid: &mut Grid<T>,
+        rule: impl Fn(Position, &T, &[T]) -> T
+    ) -> AutomatonOutcome {
+        let directions = self.neighborhood.directions();
+        let mut buffer = grid.clone();
+        let mut neighbors = Vec::new();
+        self.run(
+            grid,
+            |current| {
+                let mut changed = false;
+                for (i, cell) in current.data.iter().enumerate() {
+                    let pos = current.get_position_index(i);
+                    neighbors.clear();
+                    for dir in &directions {
+                        let adjacent = current.get_adjacent(&pos, dir);
+                        if let Some(value) = adjacent.and_then(|p| current.get(&p)) {
+                            neighbors.push(value.clone());
+                        }
+                    }
+                    let next = rule(pos, cell, &neighbors);
+                    changed |= next != *cell;
+                    buffer.data[i] = next;
+                }
+                std::mem::swap(current, &mut buffer);
+                changed
+            },
+            |grid| grid.iter().collect(),
+            |grid| {
+                let mut hasher = DefaultHasher::new();
+                grid.data.hash(&mut hasher);
+                hasher.finish()
+            },
+            |a, b| a.data == b.data
+        )
+    }
+
+    /// Steps a sparse grid, where missing cells are empty. Every stored cell and its
+    /// neighbors are evaluated; the rule returns `None` to leave a cell empty.
+    pub fn run_sparse(
+        &self,
+        grid: &mut SparseGrid<T>,
+        rule: impl Fn(Position, Option<&T>, &[T]) -> Option<T>
+    ) -> AutomatonOutcome {
+        let directions = self.neighborhood.directions();
+        let mut buffer = SparseGrid::new();
+        let mut neighbors = Vec::new();
+        self.run(
+            grid,
+            |current| {
+                let candidates: HashSet<Position> = current
+                    .iter()
+                    .flat_map(|(pos, _)| {
+                        directions
+                            .iter()
+                            .map(|dir| pos.get_adjacent(dir))
+                            .chain(std::iter::once(*pos))
+                    })
+                    .collect();
+                let mut changed = false;
+                buffer.clear();
+                for pos in candidates {
+                    neighbors.clear();
+                    for dir in &directions {
+                        if let Some(value) = current.get(&pos.get_adjacent(dir)) {
+                            neighbors.push(value.clone());
+                        }
+                    }
+                    let cell = current.get(&pos);
+                    let next = rule(pos, cell, &neighbors);
+                    changed |= next.as_ref() != cell;
+                    if let Some(next) = next {
+                        buffer.insert(pos, next);
+                    }
+                }
+                std::mem::swap(current, &mut buffer);
+                changed
+            },
+            |grid| grid.iter().map(|(pos, value)| (*pos, value)).collect(),
+            |grid| {
+                // Cells are unordered, so combine per-cell hashes commutatively
+                grid.iter().fold(0u64, |acc, cell| {
+                    let mut hasher = DefaultHasher::new();
+                    cell.hash(&mut hasher);
+                    acc.wrapping_add(hasher.finish())
+                })
+            },
+            // Bounds only grow, so compare the cells rather than the grids
+            |a, b| a.len() == b.len() && a.iter().all(|(pos, value)| b.get(pos) == Some(value))
+        )
+    }
+
+    fn run<S: Clone>(
+        &self,
+        state: &mut S,
+        mut step: impl FnMut(&mut S) -> bool,
+        cells: impl Fn(&S) -> Vec<(Position, &T)>,
+        state_hash: impl Fn(&S) -> u64,
+        same_state: impl Fn(&S, &S) -> bool
+    ) -> AutomatonOutcome {
+        // Earlier generations by hash, with the states sharing it
+        let mut seen: HashMap<u64, Vec<(usize, S)>> = HashMap::new();
+        let mut generation = 0;
+        loop {
+            if let Some((delay_ms, to_char)) = &self.animation {
+                let mut layers: HashMap<char, HashSet<Position>> = HashMap::new();
+                for (pos, value) in cells(state) {
+                    if let Some(ch) = to_char(value) {
+                        layers.entry(ch).or_default().insert(pos);
+                    }
+                }
+                display_grid_animated(
+                    layers.iter().map(|(ch, set)| (set, *ch)),
+                    generation,
+                    *delay_ms
+                );
+            }
+            if self.detect_cycles {
+                let candidates = seen.entry(state_hash(state)).or_default();
+                let repeated = candidates.iter().find(|(_, earlier)| same_state(earlier, state));
+                if let Some(&(start, _)) = repeated {
+                    return AutomatonOutcome::Cycle { start, length: generation - start };
+                }
+                candidates.push((generation, state.clone()));
+            }
+            if self.max_steps.is_some_and(|max| generation >= max) {
+                return AutomatonOutcome::StepLimit(generation);
+            }
+            if !step(state) {
+                return AutomatonOutcome::Stable(generation);
+            }
+            generation += 1;
+        }
+    }
+}
@@ -1,28 +1,113 @@
 use std::env;
-use aoc_utils::create_day_files;
+use std::fs;
+use std::path::Path;
+use aoc_utils::{ DayTemplate, Example, Puzzle, YearProject, DEFAULT_TEMPLATE };
+
+const USAGE: &str = "Usage: cargo new-day <year> <day_number> [options]
+
+Options:
+  --template <name>        Template to start from: day_template (default), grid, graph,
+                           parse, or any <name>.rs in $AOC_TEMPLATES, ~/.config/aoc/templates
+                           or templates/
+  --title <title>          Puzzle title
+  --example <file>         Example input, repeat for several examples
+  --part1 <answer>         Expected part 1 answer of the previous --example, an integer
+  --part2 <answer>         Expected part 2 answer of the previous --example, an integer
+  --dep <name>=<version>   Extra dependency of a newly created year crate";
+
+fn usage_error() -> ! {
+    eprintln!("{}", USAGE);
+    std::process::exit(1);
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut positional = Vec::new();
+    let mut dependencies = Vec::new();
+    let mut template_name = DEFAULT_TEMPLATE.to_string();
+    let mut title = None;
+    let mut examples: Vec<Example> = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| usage_error());
+        match arg.as_str() {
+            "--template" => {
+                template_name = value();
+            }
+            "--title" => {
+                title = Some(value());
+            }
+            "--example" => {
+                let path = value();
+                let input = fs::read_to_string(&path).unwrap_or_else(|e| {
+                    eprintln!("Unable to read example '{}': {}", path, e);
+                    std::process::exit(1);
+                });
+                examples.push(Example::new(&input));
+            }
+            "--part1" | "--part2" => {
+                let answer = value();
+                // The generated tests compare the answer with the usize of the solution
+                if answer.trim().parse::<i128>().is_err() {
+                    eprintln!("Answer '{}' is not an integer", answer);
+                    std::process::exit(1);
+                }
+                let Some(example) = examples.pop() else {
+                    usage_error();
+                };
+                examples.push(if arg == "--part1" {
+                    example.part1(answer.trim())
+                } else {
+                    example.part2(answer.trim())
+                });
+            }
+            "--dep" => {
+                let dep = value();
+                let Some((name, spec)) = dep.split_once('=') else {
+                    usage_error();
+                };
+                dependencies.push((name.to_string(), spec.to_string()));
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => positional.push(arg),
+        }
+    }
 
-    if args.len() != 3 {
-        eprintln!("Usage: cargo new-day <year> <day_number>");
-        std::process::exit(1);
+    if positional.len() != 2 {
+        usage_error();
     }
 
-    let year = &args[1];
-    let day = args[2].parse::<u32>().unwrap_or_else(|_| {
+    let year = &positional[0];
+    let day = positional[1].parse::<u32>().unwrap_or_else(|_| {
         eprintln!("Day must be a number");
         std::process::exit(1);
     });
 
-    if let Err(e) = create_day_files(year, day) {
+    let root = Path::new(".");
+    let template = DayTemplate::load(root, &template_name).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+    let mut puzzle = examples.into_iter().fold(Puzzle::new(day), Puzzle::example);
+    if let Some(title) = title {
+        puzzle = puzzle.title(&title);
+    }
+
+    // Dependencies only apply to a year crate created by this run
+    let project = dependencies
+        .iter()
+        .fold(YearProject::new(year), |project, (name, spec)| project.dependency(name, spec));
+    if let Err(e) = project.create_puzzle(root, &puzzle, &template) {
         eprintln!("Error creating day files: {}", e);
         std::process::exit(1);
     }
 
-    let package_name = format!("year_{}", year);
+    let package_name = project.package_name();
     let day_padded = format!("{:02}", day);
 
     println!("\nDay {} created!", day_padded);
+    println!("{}", puzzle.url(year));
     println!("cargo run --package {} --bin day{}", package_name, day_padded);
 }
@@ -0,0 +1,216 @@
+use std::env;
+use std::io::Write;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::{ Duration, Instant };
+use aoc_utils::{ spawn_key_reader, Key, RawMode, Recording };
+
+const USAGE: &str = "Usage: cargo replay <file.cast> [--speed <factor>] [--at <seconds>] \
+[--frame <index>] [--paused]";
+const CONTROLS: &str =
+    "space pause  ←/→ seek 5s  ,/. previous/next frame  +/- speed  0 restart  q quit";
+const MIN_SPEED: f64 = 1.0 / 64.0;
+const MAX_SPEED: f64 = 64.0;
+
+struct Options {
+    path: String,
+    speed: f64,
+    at: f64,
+    frame: Option<usize>,
+    paused: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        path: String::new(),
+        speed: 1.0,
+        at: 0.0,
+        frame: None,
+        paused: false,
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next().ok_or_else(|| format!("Missing value for {}", name))
+        };
+        match arg.as_str() {
+            "--speed" => {
+                options.speed = value("--speed")?
+                    .parse()
+                    .ok()
+                    .filter(|speed: &f64| *speed > 0.0)
+                    .ok_or("Speed must be a positive number")?
+                    .clamp(MIN_SPEED, MAX_SPEED);
+            }
+            "--at" => {
+                options.at = value("--at")?.parse().map_err(|_| "Time must be a number")?;
+            }
+            "--frame" => {
+                options.frame = Some(
+                    value("--frame")?.parse().map_err(|_| "Frame must be a number")?
+                );
+            }
+            "--paused" => {
+                options.paused = true;
+            }
+            path if options.path.is_empty() && !path.starts_with("--") => {
+                options.path = path.to_string();
+            }
+            other => {
+                return Err(format!("Unexpected argument '{}'", other));
+            }
+        }
+    }
+    if options.path.is_empty() {
+        return Err("Missing cast file".to_string());
+    }
+    Ok(options)
+}
+
+/// Playback position, `time` being recording seconds at the last resume or seek
+struct Player {
+    recording: Recording,
+    time: f64,
+    resumed: Instant,
+    speed: f64,
+    paused: bool,
+    shown: Option<usize>,
+}
+
+impl Player {
+    /// Recording time being shown, which stops at the end of the recording
+    fn now(&self) -> f64 {
+        if self.paused {
+            self.time
+        } else {
+            let elapsed = self.resumed.elapsed().as_secs_f64() * self.speed;
+            (self.time + elapsed).min(self.recording.duration())
+        }
+    }
+
+    fn seek(&mut self, time: f64) {
+        self.time = time.clamp(0.0, self.recording.duration());
+        self.resumed = Instant::now();
+    }
+
+    fn seek_frame(&mut self, index: usize) {
+        let index = index.min(self.recording.frames.len() - 1);
+        self.seek(self.recording.frames[index].time);
+    }
+
+    fn set_speed(&mut self, speed: f64) {
+        self.seek(self.now());
+        self.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+    }
+
+    fn toggle_pause(&mut self) {
+        self.seek(self.now());
+        self.paused = !self.paused;
+    }
+
+    /// Draws the frame on screen at the current time, if it changed
+    fn draw(&mut self, interactive: bool) {
+        let index = self.recording.frame_at(self.now());
+        if self.shown == Some(index) {
+            return;
+        }
+        let mut out = self.recording.screen_at(index);
+        if interactive {
+            out.push_str(
+                &format!(
+                    "\r\n\x1B[7m frame {}/{}  {:.2}s/{:.2}s  x{}{} \x1B[0m\r\n{}",
+                    index + 1,
+                    self.recording.frames.len(),
+                    self.recording.frames[index].time,
+                    self.recording.duration(),
+                    self.speed,
+                    if self.paused { "  paused" } else { "" },
+                    CONTROLS
+                )
+            );
+        }
+        print!("{}", out);
+        std::io::stdout().flush().unwrap();
+        self.shown = Some(index);
+    }
+
+    /// Wall clock time until the next frame is due, `None` when paused or at the end
+    fn until_next_frame(&self) -> Option<Duration> {
+        if self.paused {
+            return None;
+        }
+        let next = self.recording.frame_at(self.now()) + 1;
+        let frame = self.recording.frames.get(next)?;
+        Some(Duration::from_secs_f64(((frame.time - self.now()) / self.speed).max(0.0)))
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = parse_args(&args).unwrap_or_else(|e| {
+        eprintln!("{}\n{}", e, USAGE);
+        std::process::exit(1);
+    });
+    let recording = Recording::load(&options.path).unwrap_or_else(|e| {
+        eprintln!("Error reading recording: {}", e);
+        std::process::exit(1);
+    });
+    if recording.frames.is_empty() {
+        eprintln!("{} has no frames", options.path);
+        std::process::exit(1);
+    }
+
+    let mut player = Player {
+        recording,
+        time: 0.0,
+        resumed: Instant::now(),
+        speed: options.speed,
+        paused: options.paused,
+        shown: None,
+    };
+    match options.frame {
+        Some(index) => player.seek_frame(index),
+        None => player.seek(options.at),
+    }
+
+    // Without a terminal to read keys from, play the recording through once
+    let Ok(_raw_mode) = RawMode::enable() else {
+        player.paused = false;
+        loop {
+            player.draw(false);
+            match player.until_next_frame() {
+                Some(wait) => std::thread::sleep(wait),
+                None => return,
+            }
+        }
+    };
+
+    let keys = spawn_key_reader();
+    loop {
+        player.draw(true);
+        let key = match player.until_next_frame() {
+            Some(wait) => keys.recv_timeout(wait),
+            None => keys.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        let frame = player.recording.frame_at(player.now());
+        match key {
+            Ok(Key::Char('q') | Key::Escape | Key::Interrupt) |
+            Err(RecvTimeoutError::Disconnected) => {
+                break;
+            }
+            Ok(Key::Char(' ')) => player.toggle_pause(),
+            Ok(Key::Right) => player.seek(player.now() + 5.0),
+            Ok(Key::Left) => player.seek(player.now() - 5.0),
+            Ok(Key::Char('.')) => player.seek_frame(frame + 1),
+            Ok(Key::Char(',')) => player.seek_frame(frame.saturating_sub(1)),
+            Ok(Key::Char('+')) | Ok(Key::Up) => player.set_speed(player.speed * 2.0),
+            Ok(Key::Char('-')) | Ok(Key::Down) => player.set_speed(player.speed / 2.0),
+            Ok(Key::Char('0')) => player.seek(0.0),
+            Ok(_) | Err(RecvTimeoutError::Timeout) => {}
+        }
+        // Keys can change the status line without changing the frame
+        if key.is_ok() {
+            player.shown = None;
+        }
+    }
+    println!();
+}
@@ -0,0 +1,112 @@
+/// Group of adjacent text columns, cut out of a layout between columns made only of
+/// spaces. Every row is padded to the block width so alignment is kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnBlock {
+    /// Index of the first column of the block in the original text
+    pub start: usize,
+    pub width: usize,
+    pub rows: Vec<String>,
+}
+
+impl ColumnBlock {
+    /// Removes and returns the last row, typically a footer such as an operator line
+    pub fn pop_row(&mut self) -> Option<String> {
+        self.rows.pop()
+    }
+
+    /// Text of each column read top to bottom, from the leftmost column
+    pub fn columns(&self) -> Vec<String> {
+        let rows: Vec<Vec<char>> = self.rows
+            .iter()
+            .map(|row| row.chars().collect())
+            .collect();
+        (0..self.width)
+            .map(|x| rows.iter().map(|row| row[x]).collect())
+            .collect()
+    }
+
+    /// Text of each column read top to bottom, from the rightmost column
+    pub fn columns_right_to_left(&self) -> Vec<String> {
+        let mut columns = self.columns();
+        columns.reverse();
+        columns
+    }
+}
+
+/// Splits a text layout into blocks separated by columns that are blank on every line.
+/// Lines shorter than the longest one count as padded with spaces, and trailing blank
+/// lines are ignored.
+///
+/// # Example
+/// ```
+/// let input = "123 328\n 45 64 \n  6 98 \n*   +  ";
+/// let mut blocks = aoc_utils::column_blocks(input);
+/// assert_eq!(blocks.len(), 2);
+/// assert_eq!(blocks[1].start, 4);
+/// assert_eq!(blocks[0].rows, vec!["123", " 45", "  6", "*  "]);
+///
+/// let operator = blocks[0].pop_row();
+/// assert_eq!(operator.as_deref(), Some("*  "));
+/// assert_eq!(blocks[0].columns(), vec!["1  ", "24 ", "356"]);
+/// assert_eq!(blocks[1].columns_right_to_left()[0], "8   ");
+/// ```
+pub fn column_blocks(input: &str) -> Vec<ColumnBlock> {
+    let mut lines: Vec<Vec<char>> = input
+        .lines()
+        .map(|line| line.chars().collect())
+        .collect();
+    while lines.last().is_some_and(|line| line.iter().all(|c| c.is_whitespace())) {
+        lines.pop();
+    }
+    let width = lines.iter().map(Vec::len).max().unwrap_or(0);
+    for line in &mut lines {
+        line.resize(width, ' ');
+    }
+
+    let is_blank_column = |x: usize| lines.iter().all(|line| line[x] == ' ');
+    let mut blocks = Vec::new();
+    let mut x = 0;
+    while x < width {
+        if is_blank_column(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && !is_blank_column(x) {
+            x += 1;
+        }
+        blocks.push(ColumnBlock {
+            start,
+            width: x - start,
+            rows: lines
+                .iter()
+                .map(|line| line[start..x].iter().collect())
+                .collect(),
+        });
+    }
+    blocks
+}
+
+/// Cuts a fixed-width line into fields of the given widths. A line that is too short
+/// gives truncated or empty fields, and text past the last field is ignored.
+///
+/// # Example
+/// ```
+/// let fields = aoc_utils::fixed_width_fields("[Z] [M] [P]", &[4, 4, 4]);
+/// assert_eq!(fields, vec!["[Z] ", "[M] ", "[P]"]);
+/// ```
+pub fn fixed_width_fields<'a>(line: &'a str, widths: &[usize]) -> Vec<&'a str> {
+    let mut rest = line;
+    widths
+        .iter()
+        .map(|&width| {
+            let end = rest
+                .char_indices()
+                .nth(width)
+                .map_or(rest.len(), |(i, _)| i);
+            let (field, remaining) = rest.split_at(end);
+            rest = remaining;
+            field
+        })
+        .collect()
+}
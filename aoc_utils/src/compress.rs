@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+
+/// CRC-32 of PNG chunks and zip files
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+    for (n, entry) in table.iter_mut().enumerate() {
+        let mut c = n as u32;
+        for _ in 0..8 {
+            c = if c & 1 == 1 { 0xedb8_8320 ^ (c >> 1) } else { c >> 1 };
+        }
+        *entry = c;
+    }
+    !bytes
+        .iter()
+        .fold(!0u32, |crc, &byte| table[((crc ^ (byte as u32)) & 0xff) as usize] ^ (crc >> 8))
+}
+
+pub(crate) fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    // 5552 is the largest run that can't overflow before reducing
+    for chunk in bytes.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+/// Bit stream filled from the least significant bit of each byte, as both deflate and
+/// GIF's LZW expect
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    count: u32,
+}
+
+impl BitWriter {
+    fn write_bits(&mut self, value: u32, bits: u32) {
+        self.buffer |= value << self.count;
+        self.count += bits;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// Huffman codes are packed starting from their most significant bit
+    fn write_code(&mut self, code: u32, bits: u32) {
+        self.write_bits(code.reverse_bits() >> (32 - bits), bits);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115,
+    131, 163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+const WINDOW: usize = 32768;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const MAX_CHAIN: usize = 64;
+const HASH_BITS: u32 = 15;
+
+/// Literal/length symbol with the fixed Huffman code of RFC 1951
+fn write_symbol(out: &mut BitWriter, symbol: u32) {
+    match symbol {
+        0..=143 => out.write_code(0x30 + symbol, 8),
+        144..=255 => out.write_code(0x190 + symbol - 144, 9),
+        256..=279 => out.write_code(symbol - 256, 7),
+        _ => out.write_code(0xc0 + symbol - 280, 8),
+    }
+}
+
+fn write_match(out: &mut BitWriter, len: usize, distance: usize) {
+    let code = LENGTH_BASE.partition_point(|&base| (base as usize) <= len) - 1;
+    write_symbol(out, 257 + (code as u32));
+    out.write_bits((len - (LENGTH_BASE[code] as usize)) as u32, LENGTH_EXTRA[code] as u32);
+    let code = DISTANCE_BASE.partition_point(|&base| (base as usize) <= distance) - 1;
+    out.write_code(code as u32, 5);
+    out.write_bits(
+        (distance - (DISTANCE_BASE[code] as usize)) as u32,
+        DISTANCE_EXTRA[code] as u32
+    );
+}
+
+fn hash(bytes: &[u8]) -> usize {
+    let key = ((bytes[0] as u32) << 16) | ((bytes[1] as u32) << 8) | (bytes[2] as u32);
+    (key.wrapping_mul(0x9e37_79b1) >> (32 - HASH_BITS)) as usize
+}
+
+/// Deflate stream in a single block with the fixed Huffman codes, matches found through
+/// hash chains over the last 32 KiB
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut out = BitWriter::default();
+    // Final block, fixed Huffman codes
+    out.write_bits(1, 1);
+    out.write_bits(1, 2);
+
+    // Most recent position of each hash, and the previous one with the same hash
+    let mut head = vec![usize::MAX; 1 << HASH_BITS];
+    let mut previous = vec![usize::MAX; data.len()];
+    let insert = |head: &mut [usize], previous: &mut [usize], pos: usize| {
+        if pos + MIN_MATCH <= data.len() {
+            let h = hash(&data[pos..]);
+            previous[pos] = head[h];
+            head[h] = pos;
+        }
+    };
+
+    let mut i = 0;
+    while i < data.len() {
+        let (mut best_len, mut best_distance) = (0, 0);
+        if i + MIN_MATCH <= data.len() {
+            let max_len = MAX_MATCH.min(data.len() - i);
+            let mut candidate = head[hash(&data[i..])];
+            let mut chain = 0;
+            while candidate != usize::MAX && i - candidate <= WINDOW && chain < MAX_CHAIN {
+                let len = data[candidate..]
+                    .iter()
+                    .zip(&data[i..i + max_len])
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                if len > best_len {
+                    (best_len, best_distance) = (len, i - candidate);
+                    if len == max_len {
+                        break;
+                    }
+                }
+                candidate = previous[candidate];
+                chain += 1;
+            }
+        }
+        if best_len >= MIN_MATCH {
+            write_match(&mut out, best_len, best_distance);
+            for pos in i..i + best_len {
+                insert(&mut head, &mut previous, pos);
+            }
+            i += best_len;
+        } else {
+            write_symbol(&mut out, data[i] as u32);
+            insert(&mut head, &mut previous, i);
+            i += 1;
+        }
+    }
+    write_symbol(&mut out, 256);
+    out.finish()
+}
+
+/// zlib stream (RFC 1950) of the data
+pub(crate) fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    // Deflate with a 32 KiB window, no preset dictionary, check bits making it a
+    // multiple of 31
+    let mut out = vec![0x78, 0x01];
+    out.extend(deflate(data));
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+/// Variable width LZW codes as stored in GIF image data, before splitting in sub-blocks
+pub(crate) fn lzw_compress(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u32 << min_code_size;
+    let end = clear + 1;
+    let mut out = BitWriter::default();
+    let mut code_size = (min_code_size as u32) + 1;
+    let mut next = clear + 2;
+    let mut table: HashMap<(u32, u8), u32> = HashMap::new();
+    out.write_bits(clear, code_size);
+
+    let Some((&first, rest)) = indices.split_first() else {
+        out.write_bits(end, code_size);
+        return out.finish();
+    };
+    let mut prefix = first as u32;
+    for &index in rest {
+        if let Some(&code) = table.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+        out.write_bits(prefix, code_size);
+        table.insert((prefix, index), next);
+        next += 1;
+        // The decoder adds its entries one code late, so it widens once `next` has
+        // gone past the current width
+        if next > 1 << code_size && code_size < 12 {
+            code_size += 1;
+        }
+        if next == 4096 {
+            out.write_bits(clear, code_size);
+            table.clear();
+            code_size = (min_code_size as u32) + 1;
+            next = clear + 2;
+        }
+        prefix = index as u32;
+    }
+    out.write_bits(prefix, code_size);
+    if next == 1 << code_size && code_size < 12 {
+        code_size += 1;
+    }
+    out.write_bits(end, code_size);
+    out.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bits read from the least significant bit of each byte, mirroring `BitWriter`
+    struct BitReader<'a> {
+        bytes: &'a [u8],
+        pos: usize,
+    }
+
+    impl BitReader<'_> {
+        fn bits(&mut self, count: u32) -> u32 {
+            (0..count).fold(0, |value, i| {
+                let bit = (self.bytes[self.pos / 8] >> (self.pos % 8)) & 1;
+                self.pos += 1;
+                value | ((bit as u32) << i)
+            })
+        }
+
+        /// Huffman code, most significant bit first
+        fn code(&mut self, count: u32) -> u32 {
+            (0..count).fold(0, |code, _| (code << 1) | self.bits(1))
+        }
+
+        /// Literal/length symbol of the fixed Huffman code
+        fn symbol(&mut self) -> u32 {
+            let code = self.code(7);
+            if code <= 0x17 {
+                return 256 + code;
+            }
+            let code = (code << 1) | self.bits(1);
+            match code {
+                0x30..=0xbf => code - 0x30,
+                0xc0..=0xc7 => 280 + code - 0xc0,
+                _ => 144 + ((code << 1) | self.bits(1)) - 0x190,
+            }
+        }
+    }
+
+    /// Decodes the single fixed Huffman block written by `deflate`, returning the data
+    /// and the number of back-references it held
+    fn inflate(stream: &[u8]) -> (Vec<u8>, usize) {
+        let mut reader = BitReader { bytes: stream, pos: 0 };
+        assert_eq!(reader.bits(1), 1, "final block");
+        assert_eq!(reader.bits(2), 1, "fixed Huffman codes");
+        let (mut out, mut matches) = (Vec::new(), 0);
+        loop {
+            match reader.symbol() {
+                literal @ 0..=255 => out.push(literal as u8),
+                256 => {
+                    return (out, matches);
+                }
+                symbol => {
+                    let code = (symbol - 257) as usize;
+                    let len = (LENGTH_BASE[code] as usize) +
+                        (reader.bits(LENGTH_EXTRA[code] as u32) as usize);
+                    let code = reader.code(5) as usize;
+                    let distance = (DISTANCE_BASE[code] as usize) +
+                        (reader.bits(DISTANCE_EXTRA[code] as u32) as usize);
+                    for _ in 0..len {
+                        out.push(out[out.len() - distance]);
+                    }
+                    matches += 1;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn checksums_match_known_vectors() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        // Long enough to need the periodic reduction
+        assert_eq!(adler32(&[0xff; 100_000]), 0x149a_302c);
+    }
+
+    #[test]
+    fn zlib_stream_decodes_with_back_references() {
+        let mut seed = 7u32;
+        let noise: Vec<u8> = (0..3000)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (seed >> 16) as u8
+            })
+            .collect();
+        // Short and maximal matches, overlapping copies, and a repeat 3000 bytes back
+        let mut data = b"abcabcabcabc-banana bandana-".repeat(20);
+        data.extend(vec![b'x'; 600]);
+        data.extend(&noise);
+        data.extend(&noise);
+
+        let stream = zlib_compress(&data);
+        assert_eq!(&stream[..2], &[0x78, 0x01]);
+        assert_eq!(u16::from_be_bytes([stream[0], stream[1]]) % 31, 0);
+        let (body, checksum) = stream[2..].split_at(stream.len() - 6);
+        assert_eq!(checksum, adler32(&data).to_be_bytes());
+
+        let (decoded, matches) = inflate(body);
+        assert_eq!(decoded, data);
+        assert!(matches > 0);
+        assert!(stream.len() < data.len() / 2);
+
+        assert_eq!(inflate(&deflate(b"")), (Vec::new(), 0));
+    }
+}
@@ -0,0 +1,147 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Multiset mapping each key to how many times it occurs.
+///
+/// Evolving a `Counter` with `step` keeps one entry per distinct value, so simulations
+/// where many items share the same state stay bounded no matter how many items exist.
+///
+/// # Example
+/// ```
+/// use aoc_utils::Counter;
+/// // Every fish splits in two each day
+/// let mut fish: Counter<u8> = [3, 4, 3].into_iter().collect();
+/// for _ in 0..40 {
+///     fish = fish.step(|&timer| [(timer, 2)]);
+/// }
+/// assert_eq!(fish.total(), 3 << 40);
+/// assert_eq!(fish.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<K: Hash + Eq> {
+    counts: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq> Default for Counter<K> {
+    fn default() -> Self {
+        Counter { counts: HashMap::new() }
+    }
+}
+
+impl<K: Hash + Eq> Counter<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, key: K, count: usize) {
+        if count > 0 {
+            *self.counts.entry(key).or_insert(0) += count;
+        }
+    }
+
+    /// Number of occurrences of `key`, 0 when absent
+    pub fn get(&self, key: &K) -> usize {
+        self.counts.get(key).copied().unwrap_or(0)
+    }
+
+    pub fn remove(&mut self, key: &K) -> usize {
+        self.counts.remove(key).unwrap_or(0)
+    }
+
+    /// Adds every count of `other` into this counter
+    pub fn merge(&mut self, other: Counter<K>) {
+        for (key, count) in other.counts {
+            self.add(key, count);
+        }
+    }
+
+    /// Multiplies every count by `factor`
+    pub fn scale(&mut self, factor: usize) {
+        if factor == 0 {
+            self.counts.clear();
+            return;
+        }
+        for count in self.counts.values_mut() {
+            *count *= factor;
+        }
+    }
+
+    /// Sum of all counts
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Number of distinct keys
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, usize)> {
+        self.counts.iter().map(|(key, &count)| (key, count))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.counts.keys()
+    }
+
+    /// Keys sorted by decreasing count, then by increasing key so that ties come out
+    /// in the same order on every run
+    ///
+    /// # Example
+    /// ```
+    /// let letters: aoc_utils::Counter<char> = "abracadabra".chars().collect();
+    /// let counts = letters.most_common();
+    /// assert_eq!(counts, [(&'a', 5), (&'b', 2), (&'r', 2), (&'c', 1), (&'d', 1)]);
+    /// ```
+    pub fn most_common(&self) -> Vec<(&K, usize)>
+        where K: Ord
+    {
+        let mut entries: Vec<(&K, usize)> = self.iter().collect();
+        entries.sort_unstable_by_key(|&(key, count)| (Reverse(count), key));
+        entries
+    }
+
+    /// Replaces each key by the keys `transform` produces, each with a multiplicity
+    /// that gets multiplied by the count of the original key.
+    pub fn step<K2, I>(&self, mut transform: impl FnMut(&K) -> I) -> Counter<K2>
+        where K2: Hash + Eq, I: IntoIterator<Item = (K2, usize)>
+    {
+        let mut next = Counter::new();
+        for (key, &count) in &self.counts {
+            for (new_key, multiplicity) in transform(key) {
+                next.add(new_key, count * multiplicity);
+            }
+        }
+        next
+    }
+}
+
+impl<K: Hash + Eq> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<K: Hash + Eq> Extend<K> for Counter<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.add(key, 1);
+        }
+    }
+}
+
+impl<K: Hash + Eq> IntoIterator for Counter<K> {
+    type Item = (K, usize);
+    type IntoIter = std::collections::hash_map::IntoIter<K, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
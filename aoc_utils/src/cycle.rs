@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Cycle in the sequence `x0, f(x0), f(f(x0)), ...`: the state at index `start` is the
+/// first one to repeat, and it comes back every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Smallest index holding the same state as index `n`
+    pub fn equivalent_index(&self, n: usize) -> usize {
+        if n < self.start {
+            return n;
+        }
+        self.start + ((n - self.start) % self.length)
+    }
+}
+
+/// Floyd's tortoise and hare. Uses constant memory but calls `step` about three
+/// times per element of the sequence.
+///
+/// # Example
+/// ```
+/// // 2, 4, 16, 256 % 61 = 12, 22, 57, 16, ...
+/// let cycle = aoc_utils::floyd(2u64, |&x| (x * x) % 61);
+/// assert_eq!(cycle, aoc_utils::Cycle { start: 2, length: 4 });
+/// ```
+pub fn floyd<S: PartialEq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Brent's algorithm. Uses constant memory and fewer `step` calls than `floyd`.
+///
+/// # Example
+/// ```
+/// let cycle = aoc_utils::brent(2u64, |&x| (x * x) % 61);
+/// assert_eq!(cycle, aoc_utils::Cycle { start: 2, length: 4 });
+/// ```
+pub fn brent<S: PartialEq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Remembers every state in a hash map, calling `step` once per state.
+/// Returns `None` if `step` ends the sequence before any state repeats.
+///
+/// # Example
+/// ```
+/// // A guard walking back and forth never leaves
+/// let cycle = aoc_utils::find_cycle_or_end((0, 1), |&(pos, dir)| {
+///     let next = pos + dir;
+///     if next < 0 || next > 3 { Some((pos, -dir)) } else { Some((next, dir)) }
+/// });
+/// assert_eq!(cycle.map(|c| c.length), Some(8));
+/// ```
+pub fn find_cycle_or_end<S: Hash + Eq + Clone>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>
+) -> Option<Cycle> {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut state = initial;
+    let mut index = 0;
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            return Some(Cycle { start, length: index - start });
+        }
+        let next = step(&state)?;
+        seen.insert(state, index);
+        state = next;
+        index += 1;
+    }
+}
+
+/// Hash-based detection for sequences that never end.
+pub fn find_cycle<S: Hash + Eq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    find_cycle_or_end(initial, |state| Some(step(state))).expect("Sequence can't end")
+}
+
+/// State after `n` steps, skipping whole periods as soon as a state repeats.
+///
+/// # Example
+/// ```
+/// let state = aoc_utils::state_after(1u64, 1_000_000_000_000, |&x| (x * 3) % 7);
+/// assert_eq!(state, 4);
+/// ```
+pub fn state_after<S: Hash + Eq + Clone>(
+    initial: S,
+    n: usize,
+    mut step: impl FnMut(&S) -> S
+) -> S {
+    let mut history: Vec<S> = Vec::new();
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut state = initial;
+    for index in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle { start, length: index - start };
+            return history.swap_remove(cycle.equivalent_index(n));
+        }
+        seen.insert(state.clone(), index);
+        history.push(state.clone());
+        state = step(&state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every detector on the sequence `start, next[start], next[next[start]], ...`
+    fn detect_all(next: &[usize], start: usize) -> [Cycle; 3] {
+        let step = |&x: &usize| next[x];
+        [find_cycle(start, step), floyd(start, step), brent(start, step)]
+    }
+
+    #[test]
+    fn fixed_point_from_the_start() {
+        let expected = Cycle { start: 0, length: 1 };
+        assert_eq!(detect_all(&[0], 0), [expected; 3]);
+    }
+
+    #[test]
+    fn fixed_point_after_a_tail() {
+        // 0 -> 1 -> 2 -> 2
+        let expected = Cycle { start: 2, length: 1 };
+        assert_eq!(detect_all(&[1, 2, 2], 0), [expected; 3]);
+    }
+
+    #[test]
+    fn cycle_through_the_initial_state() {
+        let expected = Cycle { start: 0, length: 3 };
+        assert_eq!(detect_all(&[1, 2, 0], 0), [expected; 3]);
+    }
+
+    #[test]
+    fn random_sequences_agree() {
+        let mut seed = 1u64;
+        let mut random = |below: usize| {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            ((seed >> 33) as usize) % below
+        };
+        for _ in 0..500 {
+            let n = 1 + random(40);
+            let next: Vec<usize> = (0..n).map(|_| random(n)).collect();
+            let start = random(n);
+            let [expected, floyd, brent] = detect_all(&next, start);
+            assert_eq!(floyd, expected, "{next:?} from {start}");
+            assert_eq!(brent, expected, "{next:?} from {start}");
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::VecDeque;
+use std::io::Write;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Duration;
+use crate::{ spawn_key_reader, terminal_size, Key, Position, RawMode, Renderer, Viewport };
+
+type View<'f, S> = dyn Fn(&S) -> Renderer + 'f;
+type Status<'f, S> = dyn Fn(&S) -> String + 'f;
+
+const CONTROLS: &str =
+    "space run/pause  s/. step  b/, back  +/- speed  arrows/hjkl scroll  z/x zoom out/in  q quit";
+/// Lines around the grid: title, status and controls
+const CHROME_LINES: usize = 3;
+
+/// Interactive terminal viewer for a simulation: `step` advances the state and `view`
+/// draws it. Runs headless, without drawing, when stdin is not a terminal.
+///
+/// # Example
+/// ```no_run
+/// use aoc_utils::{ Debugger, Layer, Position, Renderer };
+/// let view = |pos: &Position| Renderer::new().layer(Layer::new("guard", '^', [*pos]));
+/// let guard = Debugger::new(view)
+///     .title("Guard walk")
+///     .status(|pos| format!("at {},{}", pos.x, pos.y))
+///     .delay(50)
+///     .run(Position::new(0, 20), |pos| {
+///         pos.y -= 1;
+///         pos.y > 0
+///     });
+/// assert_eq!(guard, Position::new(0, 0));
+/// ```
+pub struct Debugger<'f, S> {
+    view: Box<View<'f, S>>,
+    status: Option<Box<Status<'f, S>>>,
+    title: String,
+    delay_ms: u64,
+    history: usize,
+}
+
+/// Where the simulation and the view currently are
+struct Session<S> {
+    current: S,
+    steps: usize,
+    /// Earlier states, the latest last
+    past: VecDeque<S>,
+    /// States stepped back from, the next one last
+    future: Vec<S>,
+    finished_at: Option<usize>,
+    running: bool,
+    delay_ms: u64,
+    zoom: usize,
+    scroll: Position,
+    /// Terminal columns and rows, queried again after each key rather than every frame
+    size: (usize, usize),
+}
+
+impl<S: Clone> Session<S> {
+    fn forward(&mut self, step: &mut impl FnMut(&mut S) -> bool, history: usize) -> bool {
+        let next = match self.future.pop() {
+            Some(next) => next,
+            None if self.finished_at == Some(self.steps) => {
+                return false;
+            }
+            None => {
+                let mut next = self.current.clone();
+                if !step(&mut next) {
+                    self.finished_at = Some(self.steps + 1);
+                }
+                next
+            }
+        };
+        self.past.push_back(std::mem::replace(&mut self.current, next));
+        if self.past.len() > history {
+            self.past.pop_front();
+        }
+        self.steps += 1;
+        true
+    }
+
+    fn back(&mut self) -> bool {
+        let Some(previous) = self.past.pop_back() else {
+            return false;
+        };
+        self.future.push(std::mem::replace(&mut self.current, previous));
+        self.steps -= 1;
+        true
+    }
+}
+
+impl<'f, S: Clone> Debugger<'f, S> {
+    pub fn new(view: impl Fn(&S) -> Renderer + 'f) -> Self {
+        Debugger {
+            view: Box::new(view),
+            status: None,
+            title: String::new(),
+            delay_ms: 100,
+            history: 1000,
+        }
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Extra statistics shown on the status line
+    pub fn status(mut self, status: impl Fn(&S) -> String + 'f) -> Self {
+        self.status = Some(Box::new(status));
+        self
+    }
+
+    /// Time between steps while running, 100 ms by default and at least 1 ms
+    pub fn delay(mut self, delay_ms: u64) -> Self {
+        self.delay_ms = delay_ms;
+        self
+    }
+
+    /// Number of snapshots kept to step back, 1000 by default
+    pub fn history(mut self, snapshots: usize) -> Self {
+        self.history = snapshots;
+        self
+    }
+
+    /// Runs the simulation until `step` returns `false` or the user quits, returning
+    /// the state shown last
+    pub fn run(&self, state: S, mut step: impl FnMut(&mut S) -> bool) -> S {
+        let Ok(_raw_mode) = RawMode::enable() else {
+            let mut state = state;
+            while step(&mut state) {}
+            return state;
+        };
+        let keys = spawn_key_reader();
+        let mut session = Session {
+            scroll: (self.view)(&state).area().min,
+            current: state,
+            steps: 0,
+            past: VecDeque::new(),
+            future: Vec::new(),
+            finished_at: None,
+            running: false,
+            delay_ms: self.delay_ms.max(1),
+            zoom: 1,
+            size: terminal_size(),
+        };
+
+        loop {
+            let visible = self.draw(&mut session);
+            let key = if session.running {
+                keys.recv_timeout(Duration::from_millis(session.delay_ms))
+            } else {
+                keys.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            };
+            // Scrolling moves by a quarter of the view
+            let (page_x, page_y) = (
+                (visible.width() / 4).max(1) as i32,
+                (visible.height() / 4).max(1) as i32,
+            );
+            match key {
+                Err(RecvTimeoutError::Timeout) => {
+                    session.running = session.forward(&mut step, self.history);
+                }
+                Err(RecvTimeoutError::Disconnected) |
+                Ok(Key::Char('q') | Key::Escape | Key::Interrupt) => {
+                    break;
+                }
+                Ok(Key::Char(' ')) => {
+                    session.running = !session.running;
+                }
+                Ok(Key::Char('s') | Key::Char('.') | Key::Enter) => {
+                    session.running = false;
+                    session.forward(&mut step, self.history);
+                }
+                Ok(Key::Char('b') | Key::Char(',') | Key::Backspace) => {
+                    session.running = false;
+                    session.back();
+                }
+                Ok(Key::Char('+')) => {
+                    session.delay_ms = (session.delay_ms / 2).max(1);
+                }
+                Ok(Key::Char('-')) => {
+                    session.delay_ms = (session.delay_ms * 2).clamp(1, 10_000);
+                }
+                Ok(Key::Left | Key::Char('h')) => {
+                    session.scroll.x -= page_x;
+                }
+                Ok(Key::Right | Key::Char('l')) => {
+                    session.scroll.x += page_x;
+                }
+                Ok(Key::Up | Key::Char('k')) => {
+                    session.scroll.y -= page_y;
+                }
+                Ok(Key::Down | Key::Char('j')) => {
+                    session.scroll.y += page_y;
+                }
+                Ok(Key::Char('z')) => {
+                    session.zoom = (session.zoom * 2).min(64);
+                }
+                Ok(Key::Char('x')) => {
+                    session.zoom = (session.zoom / 2).max(1);
+                }
+                Ok(_) => {}
+            }
+            // A key press is the moment to notice a resized terminal
+            if key.is_ok() {
+                session.size = terminal_size();
+            }
+        }
+        println!();
+        session.current
+    }
+
+    /// Draws the part of the state that fits in the terminal, returning that viewport
+    fn draw(&self, session: &mut Session<S>) -> Viewport {
+        let renderer = (self.view)(&session.current);
+        let area = renderer.area();
+        let (columns, rows) = session.size;
+        let zoom = session.zoom as i32;
+        let width = (columns as i32) * zoom;
+        let height = (rows.saturating_sub(CHROME_LINES).max(1) as i32) * zoom;
+        // Keep the view inside the drawing
+        session.scroll.x = session.scroll.x.min(area.max.x - width + 1).max(area.min.x);
+        session.scroll.y = session.scroll.y.min(area.max.y - height + 1).max(area.min.y);
+        let visible = Viewport::new(
+            session.scroll,
+            Position::new(
+                (session.scroll.x + width - 1).min(area.max.x),
+                (session.scroll.y + height - 1).min(area.max.y)
+            )
+        );
+
+        let state = if session.finished_at == Some(session.steps) && session.future.is_empty() {
+            "finished"
+        } else if session.running {
+            "running"
+        } else {
+            "paused"
+        };
+        let mut status = format!(
+            "step {} | {} | {} ms | zoom 1:{} | x {}..{} y {}..{}",
+            session.steps,
+            state,
+            session.delay_ms,
+            session.zoom,
+            visible.min.x,
+            visible.max.x,
+            visible.min.y,
+            visible.max.y
+        );
+        if let Some(stats) = &self.status {
+            status.push_str(" | ");
+            status.push_str(&stats(&session.current));
+        }
+
+        let mut out = String::from("\x1B[H\x1B[2J");
+        out.push_str(&self.title);
+        out.push('\n');
+        out.push_str(&renderer.viewport(visible).zoom_out(session.zoom).render_to_string());
+        out.push_str(&format!("\x1B[7m{}\x1B[0m\n{}", status, CONTROLS));
+        print!("{}", out);
+        std::io::stdout().flush().unwrap();
+        visible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(start: u32) -> Session<u32> {
+        Session {
+            current: start,
+            steps: 0,
+            past: VecDeque::new(),
+            future: Vec::new(),
+            finished_at: None,
+            running: false,
+            delay_ms: 1,
+            zoom: 1,
+            scroll: Position::new(0, 0),
+            size: (80, 24),
+        }
+    }
+
+    #[test]
+    fn forward_stops_at_the_last_step() {
+        let mut session = session(0);
+        let mut calls = 0;
+        let mut step = |n: &mut u32| {
+            calls += 1;
+            *n += 1;
+            *n < 3
+        };
+        while session.forward(&mut step, 10) {}
+        assert_eq!((session.current, session.steps, session.finished_at), (3, 3, Some(3)));
+        assert!(!session.forward(&mut step, 10));
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn back_then_forward_replays_without_stepping() {
+        let mut session = session(0);
+        let mut calls = 0;
+        let mut step = |n: &mut u32| {
+            calls += 1;
+            *n += 1;
+            *n < 5
+        };
+        while session.forward(&mut step, 10) {}
+        assert!(session.back() && session.back());
+        assert_eq!((session.current, session.steps), (3, 3));
+        assert_eq!(session.future, [5, 4]);
+        assert!(session.forward(&mut step, 10));
+        assert!(session.forward(&mut step, 10));
+        assert!(!session.forward(&mut step, 10));
+        assert_eq!((session.current, session.steps), (5, 5));
+        assert_eq!(calls, 5);
+    }
+
+    #[test]
+    fn history_bounds_how_far_back_goes() {
+        let mut session = session(0);
+        let mut step = |n: &mut u32| {
+            *n += 1;
+            true
+        };
+        for _ in 0..5 {
+            session.forward(&mut step, 2);
+        }
+        assert_eq!(session.past, [3, 4]);
+        assert!(session.back() && session.back());
+        assert!(!session.back());
+        assert_eq!((session.current, session.steps), (3, 3));
+    }
+}
@@ -0,0 +1,214 @@
+use std::ops::RangeInclusive;
+use crate::math::Integer;
+
+/// `10^exponent`, `None` when it overflows `T`
+pub fn checked_pow10<T: Integer>(exponent: u32) -> Option<T> {
+    (0..exponent).try_fold(T::ONE, |acc, _| acc.checked_mul(T::TEN))
+}
+
+/// `10^exponent`
+///
+/// # Panics
+/// When the result overflows `T`.
+pub fn pow10<T: Integer>(exponent: u32) -> T {
+    checked_pow10(exponent).expect("power of ten overflowed")
+}
+
+/// Number of decimal digits of `n`, ignoring its sign. 0 has one digit.
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::digit_count(0u32), 1);
+/// assert_eq!(aoc_utils::digit_count(-4096i64), 4);
+/// ```
+pub fn digit_count<T: Integer>(n: T) -> u32 {
+    let mut n = n.abs_value() / T::TEN;
+    let mut count = 1;
+    while n > T::ZERO {
+        n = n / T::TEN;
+        count += 1;
+    }
+    count
+}
+
+/// Decimal digits of a non-negative number, most significant first
+///
+/// # Example
+/// ```
+/// let digits: Vec<u32> = aoc_utils::digits(90520).collect();
+/// assert_eq!(digits, vec![9, 0, 5, 2, 0]);
+/// ```
+pub fn digits<T: Integer>(n: T) -> Digits<T> {
+    assert!(!n.is_negative(), "digits of a negative number");
+    Digits {
+        remaining: n,
+        divisor: pow10(digit_count(n) - 1),
+    }
+}
+
+/// Iterator returned by `digits`
+pub struct Digits<T> {
+    remaining: T,
+    divisor: T,
+}
+
+impl<T: Integer> Iterator for Digits<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.divisor == T::ZERO {
+            return None;
+        }
+        let digit = self.remaining / self.divisor;
+        self.remaining = self.remaining % self.divisor;
+        self.divisor = self.divisor / T::TEN;
+        Some(digit)
+    }
+}
+
+/// Splits a non-negative number after its first `k` digits, like `str::split_at` on its
+/// decimal representation. Leading zeros of the right part are dropped.
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::split_at_digit(253000u64, 3), (253, 0));
+/// assert_eq!(aoc_utils::split_at_digit(1024u64, 2), (10, 24));
+/// ```
+pub fn split_at_digit<T: Integer>(n: T, k: u32) -> (T, T) {
+    let count = digit_count(n);
+    assert!(k <= count, "split after digit {} of a {} digit number", k, count);
+    let divisor = pow10(count - k);
+    (n / divisor, n % divisor)
+}
+
+/// Digits of `b` written after the digits of `a`
+///
+/// # Panics
+/// When the result overflows `T`, see `checked_concat`.
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::concat(12u64, 345), 12345);
+/// ```
+pub fn concat<T: Integer>(a: T, b: T) -> T {
+    checked_concat(a, b).expect("concatenation overflowed")
+}
+
+pub fn checked_concat<T: Integer>(a: T, b: T) -> Option<T> {
+    a.checked_mul(checked_pow10(digit_count(b))?)?.checked_add(b)
+}
+
+/// `block` written `times` times in a row
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::repeat_block(12u64, 3), 121212);
+/// ```
+pub fn repeat_block<T: Integer>(block: T, times: u32) -> T {
+    (0..times).fold(T::ZERO, |acc, _| concat(acc, block))
+}
+
+/// Whether the digits of `n` are one block repeated exactly `times` times
+///
+/// # Example
+/// ```
+/// assert!(aoc_utils::is_repetition(123123u64, 2));
+/// assert!(aoc_utils::is_repetition(1111u64, 2));
+/// assert!(!aoc_utils::is_repetition(12312u64, 2));
+/// ```
+pub fn is_repetition<T: Integer>(n: T, times: u32) -> bool {
+    let count = digit_count(n);
+    if times == 0 || !count.is_multiple_of(times) {
+        return false;
+    }
+    let (block, _) = split_at_digit(n, count / times);
+    checked_repeat_multiplier(count / times, times).is_some_and(|m| block.checked_mul(m) == Some(n))
+}
+
+/// Shortest block whose repetition gives `n`, with the number of repetitions.
+/// A number without repeating pattern is its own block, repeated once.
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::smallest_repeated_block(565656u64), (56, 3));
+/// assert_eq!(aoc_utils::smallest_repeated_block(1234u64), (1234, 1));
+/// ```
+pub fn smallest_repeated_block<T: Integer>(n: T) -> (T, u32) {
+    let count = digit_count(n);
+    (1..=count)
+        .filter(|&block_len| count.is_multiple_of(block_len))
+        .map(|block_len| count / block_len)
+        .find(|&times| is_repetition(n, times))
+        .map(|times| (split_at_digit(n, count / times).0, times))
+        .unwrap_or((n, 1))
+}
+
+/// `1` followed by `times - 1` copies of `block_len - 1` zeros then a `1`, so that
+/// `block * multiplier` repeats a `block_len` digit block `times` times
+fn checked_repeat_multiplier<T: Integer>(block_len: u32, times: u32) -> Option<T> {
+    (1..times).try_fold(T::ONE, |acc: T, _| {
+        acc.checked_mul(checked_pow10(block_len)?)?.checked_add(T::ONE)
+    })
+}
+
+/// Every positive number of `range` made of one block repeated exactly `times` times, in
+/// increasing order. The numbers are generated directly rather than by scanning the
+/// range, so this stays fast for huge ranges.
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::repeated_numbers(95u64..=1200, 2), vec![99, 1010, 1111]);
+/// ```
+pub fn repeated_numbers<T: Integer>(range: RangeInclusive<T>, times: u32) -> Vec<T> {
+    let (start, end) = (*range.start(), *range.end());
+    assert!(!start.is_negative(), "repeated numbers in a negative range");
+    let mut numbers = Vec::new();
+    if times == 0 || start > end {
+        return numbers;
+    }
+    for len in digit_count(start)..=digit_count(end) {
+        if !len.is_multiple_of(times) {
+            continue;
+        }
+        let block_len = len / times;
+        let Some(multiplier) = checked_repeat_multiplier::<T>(block_len, times) else {
+            break;
+        };
+        // Blocks can't start with a zero, and must land the product inside the range
+        let smallest_block = pow10::<T>(block_len - 1);
+        let largest_block = checked_pow10::<T>(block_len).map_or(end, |p| p - T::ONE);
+        let mut from_start = start / multiplier;
+        if start % multiplier != T::ZERO {
+            from_start = from_start + T::ONE;
+        }
+        let first = smallest_block.max(from_start);
+        let last = largest_block.min(end / multiplier);
+        let mut block = first;
+        while block <= last {
+            numbers.push(block * multiplier);
+            if block == last {
+                break;
+            }
+            block = block + T::ONE;
+        }
+    }
+    numbers
+}
+
+/// Every positive number of `range` made of one block repeated at least twice, in
+/// increasing order
+///
+/// # Example
+/// ```
+/// let numbers = aoc_utils::repeated_numbers_any(95u64..=1011);
+/// assert_eq!(numbers, vec![99, 111, 222, 333, 444, 555, 666, 777, 888, 999, 1010]);
+/// ```
+pub fn repeated_numbers_any<T: Integer>(range: RangeInclusive<T>) -> Vec<T> {
+    let max_times = digit_count(*range.end());
+    let mut numbers: Vec<T> = (2..=max_times)
+        .flat_map(|times| repeated_numbers(range.clone(), times))
+        .collect();
+    numbers.sort();
+    numbers.dedup();
+    numbers
+}
@@ -24,6 +24,29 @@ impl Direction {
         }
     }
 
+    pub fn opposite(&self) -> Direction {
+        self.turn_right().turn_right()
+    }
+
+    /// Rotates 90° clockwise
+    pub fn turn_right(&self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::NorthEast => Direction::SouthEast,
+            Direction::East => Direction::South,
+            Direction::SouthEast => Direction::SouthWest,
+            Direction::South => Direction::West,
+            Direction::SouthWest => Direction::NorthWest,
+            Direction::West => Direction::North,
+            Direction::NorthWest => Direction::NorthEast,
+        }
+    }
+
+    /// Rotates 90° counter-clockwise
+    pub fn turn_left(&self) -> Direction {
+        self.turn_right().turn_right().turn_right()
+    }
+
     /// Returns an iterator over all 8 directions
     pub fn all() -> impl Iterator<Item = Direction> {
         [
@@ -0,0 +1,274 @@
+use std::cmp::Reverse;
+use std::collections::{ BTreeMap, BTreeSet };
+use std::ops::Range;
+use std::str::FromStr;
+
+/// Run of consecutive blocks holding the same file, or free when `file` is `None`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub start: usize,
+    pub len: usize,
+    pub file: Option<usize>,
+}
+
+/// Disk stored as a run-length list of file and free segments. Free segments are
+/// kept merged and indexed by length, so the leftmost gap of a given size is found
+/// in O(k log n), `k` being the number of distinct free lengths. Merged gaps can be
+/// of any length, but distinct lengths add up to at most the `n` blocks of the disk,
+/// so `k` stays below √(2n).
+///
+/// # Example
+/// ```
+/// let mut disk: aoc_utils::Disk = "2333133121414131402".parse().unwrap();
+/// disk.compact_files();
+/// assert_eq!(disk.checksum(), 2858);
+///
+/// let mut disk: aoc_utils::Disk = "2333133121414131402".parse().unwrap();
+/// disk.compact_blocks();
+/// assert_eq!(disk.checksum(), 1928);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Disk {
+    len: usize,
+    /// Used segments as start -> (length, file id)
+    files: BTreeMap<usize, (usize, usize)>,
+    /// Free segments as start -> length, adjacent ones merged
+    free: BTreeMap<usize, usize>,
+    /// Starts of the free segments, by segment length
+    free_by_len: BTreeMap<usize, BTreeSet<usize>>,
+}
+
+impl From<Vec<Option<usize>>> for Disk {
+    fn from(blocks: Vec<Option<usize>>) -> Self {
+        let mut disk = Disk { len: blocks.len(), ..Disk::default() };
+        let mut start = 0;
+        for run in blocks.chunk_by(|a, b| a == b) {
+            match run[0] {
+                Some(file) => {
+                    disk.files.insert(start, (run.len(), file));
+                }
+                None => disk.add_free(start, run.len()),
+            }
+            start += run.len();
+        }
+        disk
+    }
+}
+
+/// Dense disk map: digits alternating between file and free space lengths, file ids
+/// counting up from 0.
+impl FromStr for Disk {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut disk = Disk::default();
+        for (i, c) in s.trim().chars().enumerate() {
+            let len = c
+                .to_digit(10)
+                .ok_or_else(|| format!("Invalid disk map digit '{}' at {}", c, i))? as usize;
+            if len == 0 {
+                continue;
+            }
+            if i % 2 == 0 {
+                disk.files.insert(disk.len, (len, i / 2));
+            } else {
+                disk.add_free(disk.len, len);
+            }
+            disk.len += len;
+        }
+        Ok(disk)
+    }
+}
+
+impl Disk {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Content of every block, expanded from the segments
+    pub fn blocks(&self) -> Vec<Option<usize>> {
+        self.segments()
+            .iter()
+            .flat_map(|segment| std::iter::repeat_n(segment.file, segment.len))
+            .collect()
+    }
+
+    /// Segments from the first block, runs of the same file being merged
+    pub fn segments(&self) -> Vec<Segment> {
+        let files = self.files
+            .iter()
+            .map(|(&start, &(len, file))| Segment { start, len, file: Some(file) });
+        let free = self.free.iter().map(|(&start, &len)| Segment { start, len, file: None });
+        let mut all: Vec<Segment> = files.chain(free).collect();
+        all.sort_unstable_by_key(|segment| segment.start);
+        let mut segments: Vec<Segment> = Vec::with_capacity(all.len());
+        for segment in all {
+            match segments.last_mut() {
+                Some(last) if last.file == segment.file => {
+                    last.len += segment.len;
+                }
+                _ => segments.push(segment),
+            }
+        }
+        segments
+    }
+
+    fn index_free(&mut self, start: usize, len: usize) {
+        self.free.insert(start, len);
+        self.free_by_len.entry(len).or_default().insert(start);
+    }
+
+    fn unindex_free(&mut self, start: usize) -> usize {
+        let len = self.free.remove(&start).expect("free segment");
+        let starts = self.free_by_len.get_mut(&len).expect("free length");
+        starts.remove(&start);
+        if starts.is_empty() {
+            self.free_by_len.remove(&len);
+        }
+        len
+    }
+
+    /// Marks `start..start + len` free, merging it with the free segments around it
+    fn add_free(&mut self, mut start: usize, mut len: usize) {
+        if len == 0 {
+            return;
+        }
+        let before = self.free.range(..start).next_back().map(|(&s, &l)| (s, l));
+        if let Some((previous, previous_len)) = before.filter(|&(s, l)| s + l == start) {
+            self.unindex_free(previous);
+            start = previous;
+            len += previous_len;
+        }
+        if self.free.contains_key(&(start + len)) {
+            len += self.unindex_free(start + len);
+        }
+        self.index_free(start, len);
+    }
+
+    /// Marks `start..start + len` used, splitting the free segment holding it
+    fn take_free(&mut self, start: usize, len: usize) {
+        let holder = self.free.range(..=start).next_back().map(|(&s, &l)| (s, l));
+        let Some((free_start, free_len)) = holder.filter(|&(s, l)| start + len <= s + l) else {
+            panic!("moving blocks onto used blocks at {}", start);
+        };
+        self.unindex_free(free_start);
+        if free_start < start {
+            self.index_free(free_start, start - free_start);
+        }
+        if start + len < free_start + free_len {
+            self.index_free(start + len, free_start + free_len - start - len);
+        }
+    }
+
+    /// Removes the used blocks of `range`, returning them as (start, length, file)
+    fn take_used(&mut self, range: Range<usize>) -> Vec<(usize, usize, usize)> {
+        let first = self.files
+            .range(..range.start)
+            .next_back()
+            .filter(|&(&start, &(len, _))| start + len > range.start)
+            .map_or(range.start, |(&start, _)| start);
+        let overlapping: Vec<usize> = self.files
+            .range(first..range.end)
+            .map(|(&start, _)| start)
+            .collect();
+        let mut taken = Vec::new();
+        for start in overlapping {
+            let (len, file) = self.files.remove(&start).unwrap();
+            let end = start + len;
+            // Keep the parts sticking out of the range
+            if start < range.start {
+                self.files.insert(start, (range.start - start, file));
+            }
+            if end > range.end {
+                self.files.insert(range.end, (end - range.end, file));
+            }
+            let (from, to) = (start.max(range.start), end.min(range.end));
+            taken.push((from, to - from, file));
+        }
+        taken
+    }
+
+    /// Start of the leftmost run of at least `len` free blocks that ends before `before`
+    pub fn find_gap(&self, len: usize, before: usize) -> Option<usize> {
+        if len == 0 {
+            return None;
+        }
+        // Free segments are maximal, so the leftmost one that is long enough holds the
+        // leftmost run
+        let gap = self.free_by_len
+            .range(len..)
+            .filter_map(|(_, starts)| starts.first())
+            .min()?;
+        (gap + len <= before).then_some(*gap)
+    }
+
+    /// Moves the blocks of `from` to start at `to`, the destination being free
+    pub fn move_blocks(&mut self, from: Range<usize>, to: usize) {
+        let len = from.len();
+        let destination_free = self.free
+            .range(..=to)
+            .next_back()
+            .is_some_and(|(&start, &free_len)| to + len <= start + free_len);
+        assert!(
+            len == 0 || destination_free,
+            "moving blocks {:?} onto used blocks at {}",
+            from,
+            to
+        );
+        let offset = from.start;
+        let moved = self.take_used(from);
+        for &(start, len, _) in &moved {
+            self.add_free(start, len);
+        }
+        for (start, len, file) in moved {
+            let target = to + start - offset;
+            self.take_free(target, len);
+            self.files.insert(target, (len, file));
+        }
+    }
+
+    /// Moves each file, by decreasing id, to the leftmost gap before it that fits it
+    /// whole. Files that don't fit anywhere stay in place.
+    pub fn compact_files(&mut self) {
+        let mut files: Vec<Segment> = self.segments()
+            .into_iter()
+            .filter(|segment| segment.file.is_some())
+            .collect();
+        files.sort_by_key(|segment| Reverse(segment.file));
+        for file in files {
+            if let Some(to) = self.find_gap(file.len, file.start) {
+                self.move_blocks(file.start..file.start + file.len, to);
+            }
+        }
+    }
+
+    /// Moves blocks from the end of the disk into the leftmost free blocks, a run at a
+    /// time, until no free block is left between used ones.
+    pub fn compact_blocks(&mut self) {
+        loop {
+            let Some((&last, &(len, _))) = self.files.last_key_value() else {
+                return;
+            };
+            let Some((&gap, &gap_len)) = self.free.first_key_value() else {
+                return;
+            };
+            if gap > last {
+                return;
+            }
+            let count = len.min(gap_len);
+            self.move_blocks(last + len - count..last + len, gap);
+        }
+    }
+
+    /// Sum of block index times file id over every used block
+    pub fn checksum(&self) -> usize {
+        self.files
+            .iter()
+            .map(|(&start, &(len, file))| file * (len * start + (len * (len - 1)) / 2))
+            .sum()
+    }
+}
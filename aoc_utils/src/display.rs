@@ -1,31 +1,180 @@
 use std::collections::HashSet;
+use std::fmt::{ self, Display };
+use std::io;
 use std::time::Duration;
-use crate::Position;
+use crate::{ Grid, Position, Recorder, SparseGrid };
 
-pub fn display_grid<'a>(collections: impl IntoIterator<Item = (&'a HashSet<Position>, char)>) {
-    let collections: Vec<_> = collections.into_iter().collect();
+/// Rectangle of positions to render, both corners included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl Viewport {
+    pub fn new(min: Position, max: Position) -> Self {
+        Viewport { min, max }
+    }
+
+    /// Smallest viewport holding every position, always including the origin so that
+    /// grids starting at 0,0 keep their margins
+    pub fn around<'a>(positions: impl IntoIterator<Item = &'a Position>) -> Self {
+        positions
+            .into_iter()
+            .fold(Viewport::new(Position::new(0, 0), Position::new(0, 0)), |view, pos| {
+                Viewport::new(
+                    Position::new(view.min.x.min(pos.x), view.min.y.min(pos.y)),
+                    Position::new(view.max.x.max(pos.x), view.max.y.max(pos.y))
+                )
+            })
+    }
+
+    pub fn of_grid<T>(grid: &Grid<T>) -> Self {
+        Viewport::new(
+            Position::new(0, 0),
+            Position::new((grid.cols as i32) - 1, (grid.rows as i32) - 1)
+        )
+    }
+
+    /// Bounds of the grid, an empty viewport for an empty grid
+    pub fn of_sparse_grid<T>(grid: &SparseGrid<T>) -> Self {
+        grid.bounds()
+            .map(|(min, max)| Viewport::new(min, max))
+            .unwrap_or(Viewport::new(Position::new(0, 0), Position::new(-1, -1)))
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1).max(0) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1).max(0) as usize
+    }
+
+    pub fn contains(&self, pos: &Position) -> bool {
+        (self.min.x..=self.max.x).contains(&pos.x) && (self.min.y..=self.max.y).contains(&pos.y)
+    }
+}
 
-    let all_positions: Vec<&Position> = collections
-        .iter()
-        .flat_map(|(set, _)| set.iter())
-        .collect();
-
-    let (max_x, max_y) = all_positions
-        .iter()
-        .fold((0, 0), |(mx, my), pos| { (mx.max(pos.x), my.max(pos.y)) });
-
-    for y in 0..=max_y {
-        for x in 0..=max_x {
-            let pos = Position { x, y };
-            let ch = collections
-                .iter()
-                .find(|(set, _)| set.contains(&pos))
-                .map(|(_, c)| *c)
-                .unwrap_or('.');
-            print!("{}", ch);
+/// Adapter to render into an `io::Write` such as stdout or a file
+///
+/// # Example
+/// ```
+/// use aoc_utils::{ Grid, IoSink, Viewport, render_grid };
+/// let grid: Grid = "#.\n.#".parse().unwrap();
+/// let mut sink = IoSink(Vec::new());
+/// render_grid(&mut sink, &grid, Viewport::of_grid(&grid), ' ').unwrap();
+/// assert_eq!(sink.0, b"#.\n.#\n");
+/// ```
+pub struct IoSink<W: io::Write>(pub W);
+
+impl<W: io::Write> fmt::Write for IoSink<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_all(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+/// Writes every row of the viewport followed by a newline, `cell` writing one position
+fn render_viewport<W: fmt::Write>(
+    out: &mut W,
+    viewport: Viewport,
+    mut cell: impl FnMut(&mut W, Position) -> fmt::Result
+) -> fmt::Result {
+    for y in viewport.min.y..=viewport.max.y {
+        for x in viewport.min.x..=viewport.max.x {
+            cell(out, Position { x, y })?;
         }
-        println!();
+        writeln!(out)?;
     }
+    Ok(())
+}
+
+/// Renders position sets, the first set holding a position giving its character.
+///
+/// # Example
+/// ```
+/// use std::collections::HashSet;
+/// use aoc_utils::{ Position, Viewport, render_layers };
+/// let walls: HashSet<Position> = [Position::new(0, 0), Position::new(1, 1)].into();
+/// let path: HashSet<Position> = [Position::new(1, 0), Position::new(1, 1)].into();
+/// let mut out = String::new();
+/// let viewport = Viewport::around(walls.iter().chain(&path));
+/// render_layers(&mut out, [(&walls, '#'), (&path, 'o')], viewport, '.').unwrap();
+/// assert_eq!(out, "#o\n.#\n");
+/// ```
+pub fn render_layers<'a, W: fmt::Write>(
+    out: &mut W,
+    layers: impl IntoIterator<Item = (&'a HashSet<Position>, char)>,
+    viewport: Viewport,
+    background: char
+) -> fmt::Result {
+    let layers: Vec<_> = layers.into_iter().collect();
+    render_viewport(out, viewport, |out, pos| {
+        let ch = layers
+            .iter()
+            .find(|(set, _)| set.contains(&pos))
+            .map_or(background, |(_, c)| *c);
+        out.write_char(ch)
+    })
+}
+
+/// Renders the cells of a grid, using `background` for positions outside of it
+pub fn render_grid<W: fmt::Write, T: Display>(
+    out: &mut W,
+    grid: &Grid<T>,
+    viewport: Viewport,
+    background: char
+) -> fmt::Result {
+    render_viewport(out, viewport, |out, pos| {
+        match grid.get(&pos) {
+            Some(value) => write!(out, "{}", value),
+            None => out.write_char(background),
+        }
+    })
+}
+
+/// Renders the cells of a sparse grid, using `background` for missing cells
+pub fn render_sparse_grid<W: fmt::Write, T: Display>(
+    out: &mut W,
+    grid: &SparseGrid<T>,
+    viewport: Viewport,
+    background: char
+) -> fmt::Result {
+    render_viewport(out, viewport, |out, pos| {
+        match grid.get(&pos) {
+            Some(value) => write!(out, "{}", value),
+            None => out.write_char(background),
+        }
+    })
+}
+
+/// Every row of the grid, each followed by a newline
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        render_grid(f, self, Viewport::of_grid(self), ' ')
+    }
+}
+
+/// Every row within the grid bounds, each followed by a newline, missing cells as `.`
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        render_sparse_grid(f, self, Viewport::of_sparse_grid(self), '.')
+    }
+}
+
+pub fn display_grid<'a>(collections: impl IntoIterator<Item = (&'a HashSet<Position>, char)>) {
+    let collections: Vec<_> = collections.into_iter().collect();
+    let viewport = Viewport::around(collections.iter().flat_map(|(set, _)| set.iter()));
+    let mut out = String::new();
+    render_layers(&mut out, collections, viewport, '.').unwrap();
+    print!("{}", out);
+}
+
+/// Prints every cell within the grid bounds, using `background` for missing cells.
+pub fn display_sparse_grid<T: Display>(grid: &SparseGrid<T>, background: char) {
+    let mut out = String::new();
+    render_sparse_grid(&mut out, grid, Viewport::of_sparse_grid(grid), background).unwrap();
+    print!("{}", out);
 }
 
 pub fn display_grid_animated<'a>(
@@ -40,6 +189,19 @@ pub fn display_grid_animated<'a>(
     std::thread::sleep(Duration::from_millis(delay_ms));
 }
 
+/// Same frame as `display_grid_animated`, added to a recording instead of printed
+pub fn record_grid_frame<'a>(
+    recorder: &mut Recorder,
+    collections: impl IntoIterator<Item = (&'a HashSet<Position>, char)>,
+    step: usize
+) {
+    let collections: Vec<_> = collections.into_iter().collect();
+    let viewport = Viewport::around(collections.iter().flat_map(|(set, _)| set.iter()));
+    let mut out = format!("Step {}\n", step);
+    render_layers(&mut out, collections, viewport, '.').unwrap();
+    recorder.frame(&out);
+}
+
 pub fn clear_screen_and_move_cursor() {
     print!("\x1B[2J\x1B[1;1H");
 }
@@ -0,0 +1,219 @@
+use std::collections::HashSet;
+use std::fmt::{ self, Write };
+use std::fs;
+use std::io;
+use crate::{ Color, Graph, NodeId };
+
+const HIGHLIGHT: Color = Color::Rgb(214, 39, 40);
+const SCC_PALETTE: [Color; 8] = [
+    Color::Rgb(174, 199, 232),
+    Color::Rgb(255, 187, 120),
+    Color::Rgb(152, 223, 138),
+    Color::Rgb(255, 152, 150),
+    Color::Rgb(197, 176, 213),
+    Color::Rgb(196, 156, 148),
+    Color::Rgb(247, 182, 210),
+    Color::Rgb(219, 219, 141),
+];
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Edge of the drawn graph, standing for a chain of `hidden` nodes when collapsed
+struct DrawnEdge {
+    from: NodeId,
+    to: NodeId,
+    hidden: Vec<NodeId>,
+    highlighted: bool,
+}
+
+/// Graphviz DOT export of a `Graph`, to be rendered with `dot -Tsvg`.
+///
+/// # Example
+/// ```
+/// use aoc_utils::{ Dot, Graph };
+/// let graph: Graph = "svr: aaa\naaa: bbb\nbbb: out\nsvr: out".parse().unwrap();
+/// let path = [0, 1, 2, 3];
+/// let dot = Dot::new().highlight_path(&path).render_to_string(&graph);
+/// assert!(dot.contains("\"svr\" -> \"aaa\" [color=\"#d62728\", penwidth=2.5];"));
+///
+/// let dot = Dot::new().collapse_chains(true).render_to_string(&graph);
+/// assert!(!dot.contains("\"bbb\";"));
+/// assert!(dot.contains("\"svr\" -> \"out\" [style=dashed, label=\"+2\""));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Dot {
+    highlighted_nodes: HashSet<NodeId>,
+    highlighted_edges: HashSet<(NodeId, NodeId)>,
+    color_sccs: bool,
+    collapse_chains: bool,
+    left_to_right: bool,
+}
+
+impl Dot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn highlight(mut self, nodes: impl IntoIterator<Item = NodeId>) -> Self {
+        self.highlighted_nodes.extend(nodes);
+        self
+    }
+
+    /// Highlights the nodes of the path and the edges between consecutive ones
+    pub fn highlight_path(mut self, path: &[NodeId]) -> Self {
+        self.highlighted_nodes.extend(path);
+        self.highlighted_edges.extend(path.windows(2).map(|pair| (pair[0], pair[1])));
+        self
+    }
+
+    /// Fills the nodes of each cycle-forming strongly connected component with its own
+    /// color
+    pub fn color_sccs(mut self, enabled: bool) -> Self {
+        self.color_sccs = enabled;
+        self
+    }
+
+    /// Replaces runs of nodes with a single incoming and outgoing edge by one dashed
+    /// edge labeled with the number of hidden nodes. Highlighted nodes are always kept.
+    pub fn collapse_chains(mut self, enabled: bool) -> Self {
+        self.collapse_chains = enabled;
+        self
+    }
+
+    /// Lays ranks out from left to right instead of top to bottom
+    pub fn left_to_right(mut self, enabled: bool) -> Self {
+        self.left_to_right = enabled;
+        self
+    }
+
+    fn is_collapsible(&self, graph: &Graph, id: NodeId) -> bool {
+        self.collapse_chains &&
+            !self.highlighted_nodes.contains(&id) &&
+            graph.predecessors(id).len() == 1 &&
+            graph.successors(id).len() == 1 &&
+            graph.successors(id)[0] != id
+    }
+
+    /// Nodes left once chains are collapsed, and the edges between them
+    fn drawn(&self, graph: &Graph) -> (Vec<bool>, Vec<DrawnEdge>) {
+        let mut kept: Vec<bool> = (0..graph.len())
+            .map(|id| !self.is_collapsible(graph, id))
+            .collect();
+        let mut visited = kept.clone();
+        let mut edges = Vec::new();
+        let mut walk_from = |from: NodeId, kept: &[bool], visited: &mut [bool]| {
+            for &first in graph.successors(from) {
+                let mut edge = DrawnEdge {
+                    from,
+                    to: first,
+                    hidden: Vec::new(),
+                    highlighted: self.highlighted_edges.contains(&(from, first)),
+                };
+                // A collapsible node has a single predecessor, so the chain can only
+                // be entered here and ends on a kept node
+                while !kept[edge.to] {
+                    let next = graph.successors(edge.to)[0];
+                    visited[edge.to] = true;
+                    edge.highlighted |= self.highlighted_edges.contains(&(edge.to, next));
+                    edge.hidden.push(edge.to);
+                    edge.to = next;
+                }
+                edges.push(edge);
+            }
+        };
+        for id in 0..graph.len() {
+            if kept[id] {
+                walk_from(id, &kept, &mut visited);
+            }
+        }
+        // Cycles made only of collapsible nodes are unreachable from kept ones, keep
+        // one node of each
+        for id in 0..graph.len() {
+            if !visited[id] {
+                kept[id] = true;
+                visited[id] = true;
+                walk_from(id, &kept, &mut visited);
+            }
+        }
+        (kept, edges)
+    }
+
+    pub fn render<W: Write>(&self, graph: &Graph, out: &mut W) -> fmt::Result {
+        let mut fill = vec![None; graph.len()];
+        if self.color_sccs {
+            let cycles = graph
+                .strongly_connected_components()
+                .into_iter()
+                .filter(|scc| scc.len() > 1 || graph.successors(scc[0]).contains(&scc[0]));
+            for (i, scc) in cycles.enumerate() {
+                for id in scc {
+                    fill[id] = Some(SCC_PALETTE[i % SCC_PALETTE.len()]);
+                }
+            }
+        }
+        let (kept, edges) = self.drawn(graph);
+
+        writeln!(out, "digraph G {{")?;
+        if self.left_to_right {
+            writeln!(out, "    rankdir=LR;")?;
+        }
+        for id in (0..graph.len()).filter(|&id| kept[id]) {
+            let mut attributes = Vec::new();
+            if let Some(color) = fill[id] {
+                attributes.push(format!("style=filled, fillcolor=\"{}\"", color.hex()));
+            }
+            if self.highlighted_nodes.contains(&id) {
+                attributes.push(format!("color=\"{}\", penwidth=3", HIGHLIGHT.hex()));
+            }
+            write!(out, "    {}", quote(graph.name(id)))?;
+            if !attributes.is_empty() {
+                write!(out, " [{}]", attributes.join(", "))?;
+            }
+            writeln!(out, ";")?;
+        }
+        for edge in edges {
+            let mut attributes = Vec::new();
+            if edge.highlighted {
+                attributes.push(format!("color=\"{}\", penwidth=2.5", HIGHLIGHT.hex()));
+            }
+            if !edge.hidden.is_empty() {
+                let names: Vec<&str> = edge.hidden
+                    .iter()
+                    .map(|&id| graph.name(id))
+                    .collect();
+                attributes.push(
+                    format!(
+                        "style=dashed, label=\"+{}\", tooltip={}",
+                        edge.hidden.len(),
+                        quote(&names.join(" → "))
+                    )
+                );
+            }
+            write!(out, "    {} -> {}", quote(graph.name(edge.from)), quote(graph.name(edge.to)))?;
+            if !attributes.is_empty() {
+                write!(out, " [{}]", attributes.join(", "))?;
+            }
+            writeln!(out, ";")?;
+        }
+        writeln!(out, "}}")
+    }
+
+    pub fn render_to_string(&self, graph: &Graph) -> String {
+        let mut out = String::new();
+        self.render(graph, &mut out).expect("Writing to a String can't fail");
+        out
+    }
+
+    pub fn save(&self, graph: &Graph, path: &str) -> io::Result<()> {
+        fs::write(path, self.render_to_string(graph))
+    }
+}
+
+impl Graph {
+    /// DOT description of the graph with default options, see `Dot` for the others
+    pub fn to_dot(&self) -> String {
+        Dot::new().render_to_string(self)
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::{ HashMap, VecDeque };
+use std::fmt::Display;
+use std::hash::Hash;
+use std::str::FromStr;
+
+pub type NodeId = usize;
+
+/// Directed graph of named nodes, ids given in order of first appearance.
+///
+/// # Example
+/// ```
+/// let graph: aoc_utils::Graph = "you: bbb ccc\nbbb: out\nccc: out".parse().unwrap();
+/// let you = graph.id("you").unwrap();
+/// assert_eq!(graph.len(), 4);
+/// assert_eq!(graph.successors(you).len(), 2);
+/// assert_eq!(graph.predecessors(graph.id("out").unwrap()).len(), 2);
+/// assert_eq!(graph.layers(), vec![vec![0], vec![1, 2], vec![3]]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    edges: Vec<Vec<NodeId>>,
+    reverse_edges: Vec<Vec<NodeId>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of the node, created if it doesn't exist yet
+    pub fn node(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.edges.push(Vec::new());
+        self.reverse_edges.push(Vec::new());
+        id
+    }
+
+    /// Every node reachable from `starts`, named after its `Display` output, so values
+    /// that print the same share a node.
+    ///
+    /// # Example
+    /// ```
+    /// // Doubling modulo 7 from 1 cycles through 1, 2 and 4
+    /// let graph = aoc_utils::Graph::from_adjacency([1], |&n| [n * 2 % 7]);
+    /// assert_eq!(graph.len(), 3);
+    /// assert_eq!(graph.successors(graph.id("4").unwrap()), &[graph.id("1").unwrap()]);
+    /// ```
+    pub fn from_adjacency<N, I>(
+        starts: impl IntoIterator<Item = N>,
+        successors: impl Fn(&N) -> I
+    ) -> Self
+        where N: Display + Hash + Eq + Clone, I: IntoIterator<Item = N>
+    {
+        let mut graph = Graph::new();
+        let mut ids: HashMap<N, NodeId> = HashMap::new();
+        let mut queue: VecDeque<N> = VecDeque::new();
+        for start in starts {
+            if !ids.contains_key(&start) {
+                ids.insert(start.clone(), graph.node(&start.to_string()));
+                queue.push_back(start);
+            }
+        }
+        while let Some(node) = queue.pop_front() {
+            let from = ids[&node];
+            for next in successors(&node) {
+                let to = match ids.get(&next) {
+                    Some(&id) => id,
+                    None => {
+                        let id = graph.node(&next.to_string());
+                        ids.insert(next.clone(), id);
+                        queue.push_back(next);
+                        id
+                    }
+                };
+                graph.add_edge_ids(from, to);
+            }
+        }
+        graph
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Adds an edge between two nodes, creating them if needed. Adding an edge twice
+    /// keeps a single one.
+    pub fn add_edge(&mut self, from: &str, to: &str) {
+        let from = self.node(from);
+        let to = self.node(to);
+        self.add_edge_ids(from, to);
+    }
+
+    pub fn add_edge_ids(&mut self, from: NodeId, to: NodeId) {
+        if !self.edges[from].contains(&to) {
+            self.edges[from].push(to);
+            self.reverse_edges[to].push(from);
+        }
+    }
+
+    pub fn successors(&self, id: NodeId) -> &[NodeId] {
+        &self.edges[id]
+    }
+
+    pub fn predecessors(&self, id: NodeId) -> &[NodeId] {
+        &self.reverse_edges[id]
+    }
+
+    /// Every edge as (from, to), grouped by source node
+    pub fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId)> + '_ {
+        self.edges
+            .iter()
+            .enumerate()
+            .flat_map(|(from, targets)| targets.iter().map(move |&to| (from, to)))
+    }
+
+    /// Nodes ordered so that every edge goes forward, `None` if the graph has a cycle
+    pub fn topological_order(&self) -> Option<Vec<NodeId>> {
+        let mut in_degree: Vec<usize> = self.reverse_edges.iter().map(Vec::len).collect();
+        let mut ready: VecDeque<NodeId> = (0..self.len())
+            .filter(|&id| in_degree[id] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(id) = ready.pop_front() {
+            order.push(id);
+            for &next in &self.edges[id] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Strongly connected components with Tarjan's algorithm, each component coming
+    /// before the ones that can reach it.
+    ///
+    /// # Example
+    /// ```
+    /// let graph: aoc_utils::Graph = "a: b\nb: a c\nc: d\nd: c".parse().unwrap();
+    /// let sccs = graph.strongly_connected_components();
+    /// assert_eq!(sccs, vec![vec![2, 3], vec![0, 1]]);
+    /// ```
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut next_index = 0;
+        let mut index: Vec<Option<usize>> = vec![None; self.len()];
+        let mut low = vec![0; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut stack: Vec<NodeId> = Vec::new();
+        let mut components = Vec::new();
+
+        for root in 0..self.len() {
+            if index[root].is_some() {
+                continue;
+            }
+            // Explicit call stack of (node, next successor to look at)
+            let mut calls = vec![(root, 0)];
+            index[root] = Some(next_index);
+            low[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (id, ref mut next)) = calls.last_mut() {
+                if let Some(&child) = self.edges[id].get(*next) {
+                    *next += 1;
+                    match index[child] {
+                        None => {
+                            index[child] = Some(next_index);
+                            low[child] = next_index;
+                            next_index += 1;
+                            stack.push(child);
+                            on_stack[child] = true;
+                            calls.push((child, 0));
+                        }
+                        Some(child_index) if on_stack[child] => {
+                            low[id] = low[id].min(child_index);
+                        }
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    low[parent] = low[parent].min(low[id]);
+                }
+                if Some(low[id]) == index[id] {
+                    let mut component = Vec::new();
+                    loop {
+                        let member = stack.pop().unwrap();
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == id {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    /// Depth first order in which every edge goes forward except those closing a cycle
+    fn acyclic_order(&self) -> Vec<NodeId> {
+        let mut visited = vec![false; self.len()];
+        let mut postorder = Vec::with_capacity(self.len());
+        // Sources first so that a cycle reached from them is entered at its entry point
+        let roots = (0..self.len())
+            .filter(|&id| self.reverse_edges[id].is_empty())
+            .chain(0..self.len());
+        for root in roots {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut stack = vec![(root, 0)];
+            while let Some((id, next)) = stack.pop() {
+                match self.edges[id].get(next) {
+                    Some(&child) => {
+                        stack.push((id, next + 1));
+                        if !visited[child] {
+                            visited[child] = true;
+                            stack.push((child, 0));
+                        }
+                    }
+                    None => postorder.push(id),
+                }
+            }
+        }
+        postorder.reverse();
+        postorder
+    }
+
+    /// Layered drawing order: each node one layer below its lowest predecessor, edges
+    /// closing a cycle being ignored, and nodes in a layer sorted by the mean position
+    /// of their predecessors to limit crossings.
+    pub fn layers(&self) -> Vec<Vec<NodeId>> {
+        let order = self.acyclic_order();
+        let mut rank = vec![0; self.len()];
+        for (i, &id) in order.iter().enumerate() {
+            rank[id] = i;
+        }
+        let mut layer = vec![0; self.len()];
+        for &id in &order {
+            for &next in &self.edges[id] {
+                if rank[next] > rank[id] {
+                    layer[next] = layer[next].max(layer[id] + 1);
+                }
+            }
+        }
+
+        let mut layers: Vec<Vec<NodeId>> = Vec::new();
+        for id in 0..self.len() {
+            if layers.len() <= layer[id] {
+                layers.resize(layer[id] + 1, Vec::new());
+            }
+            layers[layer[id]].push(id);
+        }
+        let mut position = vec![0.0; self.len()];
+        for layer in &mut layers {
+            for (i, &id) in layer.iter().enumerate() {
+                position[id] = i as f64;
+            }
+        }
+        for layer in layers.iter_mut().skip(1) {
+            let barycenter: HashMap<NodeId, f64> = layer
+                .iter()
+                .map(|&id| {
+                    let above = &self.reverse_edges[id];
+                    let mean = if above.is_empty() {
+                        position[id]
+                    } else {
+                        above.iter().map(|&p| position[p]).sum::<f64>() / (above.len() as f64)
+                    };
+                    (id, mean)
+                })
+                .collect();
+            layer.sort_by(|a, b| barycenter[a].total_cmp(&barycenter[b]));
+            for (i, &id) in layer.iter().enumerate() {
+                position[id] = i as f64;
+            }
+        }
+        layers
+    }
+}
+
+/// Adjacency lists such as `aaa: bbb ccc`, one source per line. A line without targets
+/// declares a node.
+impl FromStr for Graph {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut graph = Graph::new();
+        for (i, line) in s.lines().enumerate() {
+            let (from, targets) = line.split_once(':').unwrap_or((line, ""));
+            let from = from.trim();
+            if from.is_empty() {
+                if targets.trim().is_empty() {
+                    continue;
+                }
+                return Err(format!("Missing source node on line {}", i + 1));
+            }
+            graph.node(from);
+            for to in targets.split_whitespace() {
+                graph.add_edge(from, to);
+            }
+        }
+        Ok(graph)
+    }
+}
@@ -1,13 +1,31 @@
 use std::str::FromStr;
-use crate::{ Direction, Position };
+use crate::{ Direction, Position, Topology };
 
-pub struct Grid {
-    pub data: Vec<char>,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T = char> {
+    pub data: Vec<T>,
     pub rows: usize,
     pub cols: usize,
+    pub topology: Topology,
 }
 
-impl Grid {
+impl<T: Clone> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self {
+        Grid {
+            data: vec![fill; rows * cols],
+            rows,
+            cols,
+            topology: Topology::Bounded,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
     pub fn is_pos_valid(&self, pos: &Position) -> bool {
         pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.cols && (pos.y as usize) < self.rows
     }
@@ -20,9 +38,10 @@ impl Grid {
         Some(index)
     }
 
-    pub fn get(&self, pos: &Position) -> Option<char> {
+    pub fn get(&self, pos: &Position) -> Option<&T> {
+        // `data` is public and may have been left shorter than `rows * cols`
         let index = self.get_index(pos)?;
-        Some(self.data[index])
+        self.data.get(index)
     }
 
     pub fn get_position_index(&self, index: usize) -> Position {
@@ -31,32 +50,73 @@ impl Grid {
         Position { x, y }
     }
 
-    pub fn set(&mut self, pos: &Position, value: char) {
-        if let Some(index) = self.get_index(pos) {
-            self.data[index] = value;
+    pub fn set(&mut self, pos: &Position, value: T) {
+        if let Some(cell) = self.get_index(pos).and_then(|index| self.data.get_mut(index)) {
+            *cell = value;
         }
     }
 
-    pub fn check_position(&self, pos: &Position, dir: &Direction) -> Option<char> {
-        let new_pos = pos.get_adjacent(dir);
-        self.get(&new_pos)
+    /// Moves one step following the grid topology, returning the landing position
+    /// and the facing after the move (only stitched edges change it).
+    pub fn step(&self, pos: &Position, dir: &Direction) -> Option<(Position, Direction)> {
+        self.topology.step(pos, dir, self.rows, self.cols)
+    }
+
+    pub fn get_adjacent(&self, pos: &Position, dir: &Direction) -> Option<Position> {
+        self.step(pos, dir).map(|(next, _)| next)
+    }
+
+    /// Returns the valid neighbors of `pos` in the given directions, following the topology
+    pub fn neighbors(
+        &self,
+        pos: &Position,
+        directions: impl IntoIterator<Item = Direction>
+    ) -> Vec<Position> {
+        directions
+            .into_iter()
+            .filter_map(|dir| self.get_adjacent(pos, &dir))
+            .collect()
+    }
+
+    /// Returns an iterator over every cell in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(i, value)| (self.get_position_index(i), value))
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    pub fn check_position(&self, pos: &Position, dir: &Direction) -> Option<T> {
+        let new_pos = self.get_adjacent(pos, dir)?;
+        self.get(&new_pos).copied()
     }
 
-    pub fn check_all_directions(&self, pos: &Position) -> Vec<char> {
+    pub fn check_all_directions(&self, pos: &Position) -> Vec<T> {
         Direction::all()
             .filter_map(|dir| self.check_position(pos, &dir))
             .collect()
     }
 }
 
+/// One row per line, rows shorter than the longest one padded with spaces.
+///
+/// # Example
+/// ```
+/// let grid: aoc_utils::Grid = "  ..#\n.#\n...".parse().unwrap();
+/// assert_eq!((grid.rows, grid.cols), (3, 5));
+/// assert_eq!(grid.row(1), Some(&['.', '#', ' ', ' ', ' '][..]));
+/// assert_eq!(grid.column(4).collect::<String>(), "#  ");
+/// ```
 impl FromStr for Grid {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let cols = s
             .lines()
-            .next()
-            .map(|l| l.len())
+            .map(|l| l.chars().count())
+            .max()
             .unwrap_or(0);
         let rows = s.lines().count();
 
@@ -64,11 +124,12 @@ impl FromStr for Grid {
             return Err("Empty grid".to_string());
         }
 
+        // Short rows are padded with spaces so that every row has `cols` cells
         let data: Vec<char> = s
             .lines()
-            .flat_map(|line| line.chars())
+            .flat_map(|line| line.chars().chain(std::iter::repeat(' ')).take(cols))
             .collect();
 
-        Ok(Grid { data, rows, cols })
+        Ok(Grid { data, rows, cols, topology: Topology::Bounded })
     }
 }
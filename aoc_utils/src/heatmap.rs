@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use std::fmt::{ self, Write };
+use crate::{ Color, ColorMode, Grid, Image, Position, Svg, Viewport };
+
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+/// Cell without a value, distinct from the lowest shade
+const MISSING: char = '·';
+/// Dark blue to yellow, close to the viridis color map
+const GRADIENT: [(u8, u8, u8); 5] = [
+    (68, 1, 84),
+    (59, 82, 139),
+    (33, 145, 140),
+    (94, 201, 98),
+    (253, 231, 37),
+];
+const LEGEND_WIDTH: usize = 20;
+
+/// How values are mapped to intensities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeatScale {
+    #[default]
+    Linear,
+    /// Logarithm of the distance to the minimum, for values spanning orders of magnitude
+    Log,
+}
+
+/// How cells are drawn in the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeatStyle {
+    /// Unicode shading characters from ` ` to `█`
+    #[default]
+    Shades,
+    /// 24-bit background colors, falling back to shades when colors are disabled
+    Truecolor,
+}
+
+/// Value that a heatmap can draw, implemented for every primitive number. Integers
+/// beyond 2^53 lose precision, which doesn't show once scaled to a shade or color.
+pub trait ToF64: Copy {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_to_f64 {
+    ($($t:ty),*) => {
+        $(
+            impl ToF64 for $t {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_to_f64!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// Color of an intensity between 0 and 1 along the gradient
+fn gradient(level: f64) -> Color {
+    let position = level.clamp(0.0, 1.0) * ((GRADIENT.len() - 1) as f64);
+    let index = (position.floor() as usize).min(GRADIENT.len() - 2);
+    let t = position - (index as f64);
+    let mix = |a: u8, b: u8| ((a as f64) + ((b as f64) - (a as f64)) * t).round() as u8;
+    let (from, to) = (GRADIENT[index], GRADIENT[index + 1]);
+    Color::Rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+/// Numeric field drawn as shades or colors, in the terminal, as an image or as SVG.
+/// Positions without a value are drawn as `·`.
+///
+/// # Example
+/// ```
+/// use aoc_utils::{ ColorMode, Grid, Heatmap };
+/// let mut heights = Grid::new(3, 3, 0u8);
+/// heights.data = (0..9).collect();
+/// let heatmap = Heatmap::from_grid(&heights).color(ColorMode::Never).legend(true);
+/// assert_eq!(heatmap.render_to_string(), " ░░\n▒▒▓\n▓██\n0 ░▒▓█ 8\n");
+///
+/// let visits = std::collections::HashMap::from([
+///     (aoc_utils::Position::new(0, 0), 0usize),
+///     (aoc_utils::Position::new(2, 0), usize::MAX),
+/// ]);
+/// let heatmap = Heatmap::from_map(&visits).color(ColorMode::Never);
+/// assert_eq!(heatmap.render_to_string(), " ·█\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Heatmap {
+    values: HashMap<Position, f64>,
+    scale: HeatScale,
+    style: HeatStyle,
+    legend: bool,
+    color: ColorMode,
+    viewport: Option<Viewport>,
+}
+
+impl Heatmap {
+    pub fn new(values: impl IntoIterator<Item = (Position, f64)>) -> Self {
+        Heatmap {
+            values: values.into_iter().collect(),
+            scale: HeatScale::Linear,
+            style: HeatStyle::Shades,
+            legend: false,
+            color: ColorMode::Auto,
+            viewport: None,
+        }
+    }
+
+    pub fn from_grid<T: ToF64>(grid: &Grid<T>) -> Self {
+        let mut heatmap = Heatmap::new(grid.iter().map(|(pos, &value)| (pos, value.to_f64())));
+        heatmap.viewport = Some(Viewport::of_grid(grid));
+        heatmap
+    }
+
+    pub fn from_map<N: ToF64>(values: &HashMap<Position, N>) -> Self {
+        Heatmap::new(values.iter().map(|(&pos, &value)| (pos, value.to_f64())))
+    }
+
+    pub fn scale(mut self, scale: HeatScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn style(mut self, style: HeatStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Shows the value range and its shades or colors under the map
+    pub fn legend(mut self, enabled: bool) -> Self {
+        self.legend = enabled;
+        self
+    }
+
+    pub fn color(mut self, mode: ColorMode) -> Self {
+        self.color = mode;
+        self
+    }
+
+    /// Area to draw, by default the grid or the bounds of the values
+    pub fn viewport(mut self, viewport: Viewport) -> Self {
+        self.viewport = Some(viewport);
+        self
+    }
+
+    fn area(&self) -> Viewport {
+        self.viewport.unwrap_or_else(|| {
+            let mut positions = self.values.keys();
+            let Some(&first) = positions.next() else {
+                return Viewport::new(Position::new(0, 0), Position::new(-1, -1));
+            };
+            positions.fold(Viewport::new(first, first), |view, pos| {
+                Viewport::new(
+                    Position::new(view.min.x.min(pos.x), view.min.y.min(pos.y)),
+                    Position::new(view.max.x.max(pos.x), view.max.y.max(pos.y))
+                )
+            })
+        })
+    }
+
+    /// Smallest and largest values, (0, 0) without values
+    pub fn range(&self) -> (f64, f64) {
+        if self.values.is_empty() {
+            return (0.0, 0.0);
+        }
+        self.values
+            .values()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| (min.min(v), max.max(v)))
+    }
+
+    /// Intensity of a value between 0 and 1 within `(min, max)`, 1 when every value is
+    /// the same
+    fn level(&self, value: f64, (min, max): (f64, f64)) -> f64 {
+        if max <= min {
+            return 1.0;
+        }
+        match self.scale {
+            HeatScale::Linear => (value - min) / (max - min),
+            HeatScale::Log => (value - min).ln_1p() / (max - min).ln_1p(),
+        }
+    }
+
+    fn shade(level: f64) -> char {
+        SHADES[(level * ((SHADES.len() - 1) as f64)).round() as usize]
+    }
+
+    pub fn render<W: Write>(&self, out: &mut W) -> fmt::Result {
+        let colored = self.style == HeatStyle::Truecolor && self.color.enabled();
+        let range = self.range();
+        let cell = |out: &mut W, level: Option<f64>| -> fmt::Result {
+            match level {
+                None => out.write_char(MISSING),
+                Some(level) if colored => {
+                    let (r, g, b) = gradient(level).rgb();
+                    write!(out, "\x1B[48;2;{};{};{}m \x1B[0m", r, g, b)
+                }
+                Some(level) => out.write_char(Heatmap::shade(level)),
+            }
+        };
+
+        let viewport = self.area();
+        for y in viewport.min.y..=viewport.max.y {
+            for x in viewport.min.x..=viewport.max.x {
+                let value = self.values.get(&Position { x, y });
+                cell(out, value.map(|&value| self.level(value, range)))?;
+            }
+            writeln!(out)?;
+        }
+
+        if self.legend {
+            let (min, max) = range;
+            write!(out, "{} ", min)?;
+            let steps = if colored { LEGEND_WIDTH } else { SHADES.len() - 1 };
+            for i in 1..=steps {
+                cell(out, Some((i as f64) / (steps as f64)))?;
+            }
+            write!(out, " {}", max)?;
+            if self.scale == HeatScale::Log {
+                write!(out, " (log)")?;
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    pub fn render_to_string(&self) -> String {
+        let mut out = String::new();
+        self.render(&mut out).expect("Writing to a String can't fail");
+        out
+    }
+
+    pub fn print(&self) {
+        print!("{}", self.render_to_string());
+    }
+
+    /// Cells as `scale`×`scale` squares colored along the gradient, missing ones black
+    pub fn image(&self, scale: usize) -> Image {
+        let range = self.range();
+        Image::from_cells(self.area(), scale, |pos| {
+            self.values
+                .get(&pos)
+                .map_or(Color::Rgb(0, 0, 0), |&value| gradient(self.level(value, range)))
+        })
+    }
+
+    /// One colored square per value, in reading order
+    pub fn svg(&self) -> Svg {
+        let (viewport, range) = (self.area(), self.range());
+        let mut cells: Vec<(Position, f64)> = self.values
+            .iter()
+            .filter(|(pos, _)| viewport.contains(pos))
+            .map(|(&pos, &value)| (pos, value))
+            .collect();
+        cells.sort_by_key(|(pos, _)| (pos.y, pos.x));
+        cells
+            .into_iter()
+            .fold(Svg::new(), |svg, (pos, value)| svg.cell(pos, gradient(self.level(value, range))))
+    }
+}
@@ -0,0 +1,355 @@
+use std::str::FromStr;
+
+/// Hex tile in axial coordinates. The implicit third cube coordinate is `s = -q - r`.
+///
+/// Both flat-top and pointy-top layouts share the same axial neighbors, only the
+/// names of the six directions differ (see `FlatHexDirection` and `PointyHexDirection`).
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+/// Axial offsets of the six neighbors, in counter-clockwise order starting east.
+const HEX_DELTAS: [(i32, i32); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+
+pub trait HexDirection {
+    /// Axial `(dq, dr)` offset of one step in this direction
+    fn delta(&self) -> (i32, i32);
+}
+
+impl Hex {
+    pub fn new(q: i32, r: i32) -> Self {
+        Hex { q, r }
+    }
+
+    /// Builds a hex from cube coordinates, which must satisfy `q + r + s == 0`.
+    pub fn from_cube(q: i32, r: i32, s: i32) -> Option<Self> {
+        if q + r + s != 0 {
+            return None;
+        }
+        Some(Hex { q, r })
+    }
+
+    pub fn s(&self) -> i32 {
+        -self.q - self.r
+    }
+
+    pub fn to_cube(&self) -> (i32, i32, i32) {
+        (self.q, self.r, self.s())
+    }
+
+    pub fn get_adjacent(&self, direction: &impl HexDirection) -> Hex {
+        let (dq, dr) = direction.delta();
+        Hex {
+            q: self.q + dq,
+            r: self.r + dr,
+        }
+    }
+
+    /// Returns an iterator over the 6 neighboring hexes
+    pub fn neighbors(&self) -> impl Iterator<Item = Hex> {
+        let hex = *self;
+        HEX_DELTAS.into_iter().map(move |(dq, dr)| Hex::new(hex.q + dq, hex.r + dr))
+    }
+
+    /// Number of steps between two hexes.
+    ///
+    /// # Example
+    /// ```
+    /// use aoc_utils::Hex;
+    /// assert_eq!(Hex::new(0, 0).distance(&Hex::new(3, -1)), 3);
+    /// ```
+    pub fn distance(&self, other: &Hex) -> i32 {
+        let dq = (self.q - other.q).abs();
+        let dr = (self.r - other.r).abs();
+        let ds = (self.s() - other.s()).abs();
+        dq.max(dr).max(ds)
+    }
+
+    /// All hexes exactly `radius` steps away, walking the ring counter-clockwise.
+    /// A radius of 0 yields the hex itself.
+    pub fn ring(&self, radius: u32) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![*self];
+        }
+        let radius = radius as i32;
+        let (start_dq, start_dr) = HEX_DELTAS[4];
+        let mut hex = Hex::new(self.q + start_dq * radius, self.r + start_dr * radius);
+        let mut ring = Vec::with_capacity(6 * (radius as usize));
+        for (dq, dr) in HEX_DELTAS {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex = Hex::new(hex.q + dq, hex.r + dr);
+            }
+        }
+        ring
+    }
+
+    /// All hexes within `radius` steps, ring by ring from the center outwards.
+    pub fn spiral(&self, radius: u32) -> Vec<Hex> {
+        (0..=radius).flat_map(|r| self.ring(r)).collect()
+    }
+}
+
+/// Directions on a flat-top hex grid: `n, ne, se, s, sw, nw`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum FlatHexDirection {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+impl FlatHexDirection {
+    /// Returns an iterator over all 6 directions
+    pub fn all() -> impl Iterator<Item = FlatHexDirection> {
+        [
+            FlatHexDirection::North,
+            FlatHexDirection::NorthEast,
+            FlatHexDirection::SouthEast,
+            FlatHexDirection::South,
+            FlatHexDirection::SouthWest,
+            FlatHexDirection::NorthWest,
+        ].into_iter()
+    }
+
+    /// Parses a path of directions, either separated (`"ne,ne,s"`) or run together (`"nenes"`).
+    pub fn parse_path(s: &str) -> Result<Vec<Self>, String> {
+        parse_hex_path(s)
+    }
+}
+
+impl HexDirection for FlatHexDirection {
+    fn delta(&self) -> (i32, i32) {
+        match self {
+            FlatHexDirection::North => (0, -1),
+            FlatHexDirection::NorthEast => (1, -1),
+            FlatHexDirection::SouthEast => (1, 0),
+            FlatHexDirection::South => (0, 1),
+            FlatHexDirection::SouthWest => (-1, 1),
+            FlatHexDirection::NorthWest => (-1, 0),
+        }
+    }
+}
+
+impl FromStr for FlatHexDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "n" => Ok(FlatHexDirection::North),
+            "ne" => Ok(FlatHexDirection::NorthEast),
+            "se" => Ok(FlatHexDirection::SouthEast),
+            "s" => Ok(FlatHexDirection::South),
+            "sw" => Ok(FlatHexDirection::SouthWest),
+            "nw" => Ok(FlatHexDirection::NorthWest),
+            _ => Err(format!("Invalid flat hex direction: {}", s)),
+        }
+    }
+}
+
+/// Directions on a pointy-top hex grid: `e, ne, nw, w, sw, se`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum PointyHexDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+impl PointyHexDirection {
+    /// Returns an iterator over all 6 directions
+    pub fn all() -> impl Iterator<Item = PointyHexDirection> {
+        [
+            PointyHexDirection::East,
+            PointyHexDirection::NorthEast,
+            PointyHexDirection::NorthWest,
+            PointyHexDirection::West,
+            PointyHexDirection::SouthWest,
+            PointyHexDirection::SouthEast,
+        ].into_iter()
+    }
+
+    /// Parses a path of directions, either separated (`"e,se,w"`) or run together (`"esew"`).
+    ///
+    /// # Example
+    /// ```
+    /// use aoc_utils::{ Hex, PointyHexDirection };
+    /// let path = PointyHexDirection::parse_path("nwwswee").unwrap();
+    /// let end = path.iter().fold(Hex::new(0, 0), |hex, dir| hex.get_adjacent(dir));
+    /// assert_eq!(end, Hex::new(0, 0));
+    /// ```
+    pub fn parse_path(s: &str) -> Result<Vec<Self>, String> {
+        parse_hex_path(s)
+    }
+}
+
+impl HexDirection for PointyHexDirection {
+    fn delta(&self) -> (i32, i32) {
+        match self {
+            PointyHexDirection::East => (1, 0),
+            PointyHexDirection::NorthEast => (1, -1),
+            PointyHexDirection::NorthWest => (0, -1),
+            PointyHexDirection::West => (-1, 0),
+            PointyHexDirection::SouthWest => (-1, 1),
+            PointyHexDirection::SouthEast => (0, 1),
+        }
+    }
+}
+
+impl FromStr for PointyHexDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "e" => Ok(PointyHexDirection::East),
+            "ne" => Ok(PointyHexDirection::NorthEast),
+            "nw" => Ok(PointyHexDirection::NorthWest),
+            "w" => Ok(PointyHexDirection::West),
+            "sw" => Ok(PointyHexDirection::SouthWest),
+            "se" => Ok(PointyHexDirection::SouthEast),
+            _ => Err(format!("Invalid pointy hex direction: {}", s)),
+        }
+    }
+}
+
+/// Greedily reads two-letter directions before one-letter ones, skipping commas and whitespace.
+fn parse_hex_path<D: FromStr<Err = String>>(s: &str) -> Result<Vec<D>, String> {
+    let chars: Vec<char> = s
+        .chars()
+        .filter(|c| *c != ',' && !c.is_whitespace())
+        .collect();
+    let mut path = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if i + 1 < chars.len() {
+            let pair: String = chars[i..i + 2].iter().collect();
+            if let Ok(dir) = pair.parse::<D>() {
+                path.push(dir);
+                i += 2;
+                continue;
+            }
+        }
+        path.push(chars[i].to_string().parse::<D>()?);
+        i += 1;
+    }
+    Ok(path)
+}
+
+/// Dense storage for hex tiles, laid out as a `rows`×`cols` rectangle in axial space
+/// starting at `origin`. Mirrors `Grid` so that hex cellular automata can be run on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexGrid<T> {
+    pub data: Vec<T>,
+    pub origin: Hex,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl<T: Clone> HexGrid<T> {
+    /// Creates a grid covering every hex with `min.q <= q <= max.q` and `min.r <= r <= max.r`.
+    pub fn new(min: Hex, max: Hex, fill: T) -> Self {
+        let cols = (max.q - min.q + 1).max(0) as usize;
+        let rows = (max.r - min.r + 1).max(0) as usize;
+        HexGrid {
+            data: vec![fill; rows * cols],
+            origin: min,
+            rows,
+            cols,
+        }
+    }
+
+    /// Creates a grid large enough to hold every hex within `radius` of the origin.
+    pub fn with_radius(radius: u32, fill: T) -> Self {
+        let radius = radius as i32;
+        HexGrid::new(Hex::new(-radius, -radius), Hex::new(radius, radius), fill)
+    }
+
+    /// Builds the smallest grid containing all given cells, other tiles being `fill`.
+    pub fn from_cells(cells: impl IntoIterator<Item = (Hex, T)>, fill: T) -> Self {
+        let cells: Vec<(Hex, T)> = cells.into_iter().collect();
+        if cells.is_empty() {
+            return HexGrid::new(Hex::new(0, 0), Hex::new(-1, -1), fill);
+        }
+        let (min, max) = cells.iter().fold(
+            (Hex::new(i32::MAX, i32::MAX), Hex::new(i32::MIN, i32::MIN)),
+            |(min, max), (hex, _)| {
+                (
+                    Hex::new(min.q.min(hex.q), min.r.min(hex.r)),
+                    Hex::new(max.q.max(hex.q), max.r.max(hex.r)),
+                )
+            }
+        );
+        let mut grid = HexGrid::new(min, max, fill);
+        for (hex, value) in cells {
+            grid.set(&hex, value);
+        }
+        grid
+    }
+
+    /// Adds `margin` tiles of `fill` on every side, keeping existing coordinates unchanged.
+    pub fn grow(&mut self, margin: usize, fill: T) {
+        let margin_i = margin as i32;
+        let min = Hex::new(self.origin.q - margin_i, self.origin.r - margin_i);
+        let max = Hex::new(
+            self.origin.q + (self.cols as i32) - 1 + margin_i,
+            self.origin.r + (self.rows as i32) - 1 + margin_i
+        );
+        let mut grown = HexGrid::new(min, max, fill);
+        for (hex, value) in self.iter() {
+            grown.set(&hex, value.clone());
+        }
+        *self = grown;
+    }
+}
+
+impl<T> HexGrid<T> {
+    pub fn is_hex_valid(&self, hex: &Hex) -> bool {
+        let q = hex.q - self.origin.q;
+        let r = hex.r - self.origin.r;
+        q >= 0 && r >= 0 && (q as usize) < self.cols && (r as usize) < self.rows
+    }
+
+    pub fn get_index(&self, hex: &Hex) -> Option<usize> {
+        if !self.is_hex_valid(hex) {
+            return None;
+        }
+        let q = (hex.q - self.origin.q) as usize;
+        let r = (hex.r - self.origin.r) as usize;
+        Some(r * self.cols + q)
+    }
+
+    pub fn get(&self, hex: &Hex) -> Option<&T> {
+        let index = self.get_index(hex)?;
+        Some(&self.data[index])
+    }
+
+    pub fn get_hex_index(&self, index: usize) -> Hex {
+        let q = (index % self.cols) as i32;
+        let r = (index / self.cols) as i32;
+        Hex::new(self.origin.q + q, self.origin.r + r)
+    }
+
+    pub fn set(&mut self, hex: &Hex, value: T) {
+        if let Some(index) = self.get_index(hex) {
+            self.data[index] = value;
+        }
+    }
+
+    /// Returns the in-bounds neighbors of a hex along with their values
+    pub fn neighbors(&self, hex: &Hex) -> impl Iterator<Item = (Hex, &T)> {
+        hex.neighbors().filter_map(move |n| self.get(&n).map(|value| (n, value)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Hex, &T)> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(i, value)| (self.get_hex_index(i), value))
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use crate::compress::{ crc32, lzw_compress, zlib_compress };
+use crate::{ Color, Grid, Position, Viewport };
+
+const BACKGROUND: Color = Color::Ansi256(0);
+
+/// RGB picture that can be saved as PNG or put together with others in an animated GIF.
+///
+/// # Example
+/// ```
+/// use aoc_utils::{ Color, Grid, Image };
+/// let grid: Grid = "#.\n.#".parse().unwrap();
+/// let image = Image::from_grid(&grid, 4, |&c| {
+///     if c == '#' { Color::Rgb(255, 200, 0) } else { Color::Ansi256(0) }
+/// });
+/// assert_eq!((image.width, image.height), (8, 8));
+/// assert_eq!(image.pixel(5, 7), (255, 200, 0));
+/// assert_eq!(image.pixel(3, 4), (0, 0, 0));
+/// assert!(image.to_png().starts_with(b"\x89PNG\r\n\x1a\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Color) -> Self {
+        let (r, g, b) = background.rgb();
+        Image { width, height, pixels: vec![[r, g, b]; width * height] }
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> (u8, u8, u8) {
+        let [r, g, b] = self.pixels[y * self.width + x];
+        (r, g, b)
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
+        let (r, g, b) = color.rgb();
+        self.pixels[y * self.width + x] = [r, g, b];
+    }
+
+    /// Fills the rectangle, clipped to the image
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, color: Color) {
+        for y in y..(y + height).min(self.height) {
+            for x in x..(x + width).min(self.width) {
+                self.set_pixel(x, y, color);
+            }
+        }
+    }
+
+    /// Draws every position of the viewport as a `scale`×`scale` square
+    pub fn from_cells(
+        viewport: Viewport,
+        scale: usize,
+        color_of: impl Fn(Position) -> Color
+    ) -> Self {
+        let mut image = Image::new(
+            viewport.width() * scale,
+            viewport.height() * scale,
+            BACKGROUND
+        );
+        for row in 0..viewport.height() {
+            for col in 0..viewport.width() {
+                let pos = Position::new(
+                    viewport.min.x + (col as i32),
+                    viewport.min.y + (row as i32)
+                );
+                image.fill_rect(col * scale, row * scale, scale, scale, color_of(pos));
+            }
+        }
+        image
+    }
+
+    /// Draws every cell of the grid, cells missing from a ragged grid staying black
+    pub fn from_grid<T>(grid: &Grid<T>, scale: usize, color_of: impl Fn(&T) -> Color) -> Self {
+        Image::from_cells(Viewport::of_grid(grid), scale, |pos| {
+            grid.get(&pos).map_or(BACKGROUND, &color_of)
+        })
+    }
+
+    /// 8-bit RGB PNG, rows unfiltered and deflated
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut scanlines = Vec::with_capacity(self.height * (1 + 3 * self.width));
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            scanlines.push(0);
+            scanlines.extend(row.iter().flatten());
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // Bit depth 8, truecolor, deflate, no filtering method variants, not interlaced
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_png_chunk(&mut png, b"IHDR", &header);
+        write_png_chunk(&mut png, b"IDAT", &zlib_compress(&scanlines));
+        write_png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    pub fn save_png(&self, path: &str) -> io::Result<()> {
+        fs::write(path, self.to_png())
+    }
+}
+
+fn write_png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Looping animated GIF of same sized frames shown `delay_ms` apart, rounded to the
+/// hundredth of a second GIF stores. The frames can use at most 256 colors in total.
+///
+/// # Example
+/// ```
+/// use aoc_utils::{ Color, Image, encode_gif };
+/// let mut frames = vec![Image::new(3, 2, Color::Rgb(0, 0, 0)); 2];
+/// frames[1].set_pixel(1, 1, Color::Rgb(255, 0, 0));
+/// let gif = encode_gif(&frames, 100).unwrap();
+/// assert!(gif.starts_with(b"GIF89a\x03\x00\x02\x00"));
+/// assert_eq!(gif.last(), Some(&0x3b));
+/// ```
+pub fn encode_gif(frames: &[Image], delay_ms: u64) -> Result<Vec<u8>, String> {
+    let first = frames.first().ok_or("No frames to encode")?;
+    let (width, height) = (first.width, first.height);
+    if width > 0xffff || height > 0xffff {
+        return Err(format!("{}x{} is too large for a GIF", width, height));
+    }
+    let mismatch = frames.iter().find(|frame| (frame.width, frame.height) != (width, height));
+    if let Some(frame) = mismatch {
+        return Err(
+            format!("Frame of {}x{} in a {}x{} animation", frame.width, frame.height, width, height)
+        );
+    }
+
+    let mut palette: Vec<[u8; 3]> = Vec::new();
+    let mut palette_index: HashMap<[u8; 3], u8> = HashMap::new();
+    let mut indexed_frames = Vec::with_capacity(frames.len());
+    for frame in frames {
+        let mut indices = Vec::with_capacity(frame.pixels.len());
+        for &pixel in &frame.pixels {
+            let index = match palette_index.get(&pixel) {
+                Some(&index) => index,
+                None if palette.len() < 256 => {
+                    palette.push(pixel);
+                    palette_index.insert(pixel, (palette.len() - 1) as u8);
+                    (palette.len() - 1) as u8
+                }
+                None => {
+                    return Err("More than 256 colors in the animation".to_string());
+                }
+            };
+            indices.push(index);
+        }
+        indexed_frames.push(indices);
+    }
+    // Color table of 2^(size + 1) entries, LZW needing codes of at least 2 bits
+    let table_size = (palette.len().max(2).next_power_of_two().trailing_zeros() - 1) as u8;
+    palette.resize(2 << table_size, [0, 0, 0]);
+    let min_code_size = (table_size + 1).max(2);
+    let delay = ((delay_ms + 5) / 10).min(0xffff) as u16;
+
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend((width as u16).to_le_bytes());
+    gif.extend((height as u16).to_le_bytes());
+    // Global color table, 8 bits per primary
+    gif.extend([0xf0 | table_size, 0, 0]);
+    gif.extend(palette.iter().flatten());
+    // Application extension making viewers loop forever
+    gif.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    for indices in indexed_frames {
+        // Graphic control extension: leave the frame in place, then wait `delay`
+        gif.extend([0x21, 0xf9, 0x04, 0x04]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0, 0]);
+        // Image descriptor covering the whole screen, no local color table
+        gif.push(0x2c);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend((width as u16).to_le_bytes());
+        gif.extend((height as u16).to_le_bytes());
+        gif.push(0);
+
+        gif.push(min_code_size);
+        for block in lzw_compress(&indices, min_code_size).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0);
+    }
+    gif.push(0x3b);
+    Ok(gif)
+}
+
+pub fn save_gif(path: &str, frames: &[Image], delay_ms: u64) -> Result<(), String> {
+    let gif = encode_gif(frames, delay_ms)?;
+    fs::write(path, gif).map_err(|e| format!("Unable to write {}: {}", path, e))
+}
@@ -1,18 +1,132 @@
 use std::panic::Location;
 use std::path::Path;
-use std::fs::{ self, File };
-use std::io::Write;
-use std::process::Command;
+use std::fs;
 
 mod direction;
 mod position;
 mod grid;
+mod sparse_grid;
+mod topology;
+mod transform;
+mod automaton;
+mod cycle;
+mod memo;
+mod counter;
+mod solver;
+mod math;
+mod digits;
+mod parse;
+mod columns;
+mod sequence;
+mod disk;
 mod display;
+mod renderer;
+mod recorder;
+mod compress;
+mod image;
+mod graph;
+mod svg;
+mod heatmap;
+mod dot;
+mod terminal;
+mod debugger;
+mod hex;
+mod scaffold;
+mod template;
 
 pub use direction::Direction;
 pub use position::Position;
 pub use grid::Grid;
-pub use display::{ display_grid, display_grid_animated, clear_screen_and_move_cursor };
+pub use sparse_grid::SparseGrid;
+pub use topology::{ Topology, EdgeMap };
+pub use transform::GridView;
+pub use automaton::{ Automaton, AutomatonOutcome, Neighborhood };
+pub use cycle::{ Cycle, floyd, brent, find_cycle, find_cycle_or_end, state_after };
+pub use memo::{ Memo, SharedMemo };
+pub use counter::Counter;
+pub use solver::{ Rational, LinearSystem, Gf2System, minimize_sum_ilp };
+pub use math::{
+    Integer,
+    SignedInteger,
+    gcd,
+    lcm,
+    checked_lcm,
+    gcd_all,
+    lcm_all,
+    checked_lcm_all,
+    extended_gcd,
+    mod_mul,
+    mod_pow,
+    mod_inverse,
+    crt,
+    checked_crt,
+    isqrt,
+    divisors,
+};
+pub use digits::{
+    Digits,
+    pow10,
+    checked_pow10,
+    digit_count,
+    digits,
+    split_at_digit,
+    concat,
+    checked_concat,
+    repeat_block,
+    is_repetition,
+    smallest_repeated_block,
+    repeated_numbers,
+    repeated_numbers_any,
+};
+pub use parse::{
+    ParseError,
+    Section,
+    Fields,
+    parse_value,
+    parse_within,
+    ints,
+    parse_lines,
+    sections,
+    range,
+    bracketed,
+    key_values,
+    key_value_map,
+    scan,
+};
+pub use columns::{ ColumnBlock, column_blocks, fixed_width_fields };
+pub use sequence::{
+    max_subsequence,
+    min_subsequence,
+    next_greater,
+    sliding_window_max,
+    sliding_window_min,
+    longest_increasing_subsequence,
+};
+pub use disk::{ Disk, Segment };
+pub use display::{
+    Viewport,
+    IoSink,
+    render_layers,
+    render_grid,
+    render_sparse_grid,
+    display_grid,
+    display_sparse_grid,
+    display_grid_animated,
+    record_grid_frame,
+    clear_screen_and_move_cursor,
+};
+pub use renderer::{ Color, ColorMode, Layer, Renderer };
+pub use recorder::{ Frame, Recording, Recorder, CLEAR_SCREEN };
+pub use image::{ Image, encode_gif, save_gif };
+pub use graph::{ Graph, NodeId };
+pub use svg::{ Projection, Svg };
+pub use heatmap::{ Heatmap, HeatScale, HeatStyle, ToF64 };
+pub use dot::Dot;
+pub use terminal::{ Key, RawMode, spawn_key_reader, terminal_size };
+pub use debugger::Debugger;
+pub use hex::{ Hex, HexDirection, FlatHexDirection, PointyHexDirection, HexGrid };
+pub use scaffold::{ YearProject, add_workspace_member, register_day };
+pub use template::{ DayTemplate, Example, Puzzle, DEFAULT_TEMPLATE, TEMPLATE_DIR_VAR };
 
 /// Creates all unique pairs from a slice of items.
 /// Returns a vector of tuples containing references to pairs of items.
@@ -40,7 +154,7 @@ pub fn get_input_for_day(is_test: bool) -> String {
 
     let day = file_path
         .split('/')
-        .last()
+        .next_back()
         .unwrap()
         .strip_prefix("day")
         .unwrap()
@@ -65,101 +179,8 @@ fn get_input(year: &str, day: &str, is_test: bool) -> String {
     )
 }
 
-fn create_year_project(year: &str) -> std::io::Result<()> {
-    let package_name = format!("year_{}", year);
-
-    // Run cargo new to create the project (automatically added to workspace)
-    let status = Command::new("cargo")
-        .args(["new", &package_name])
-        .status()
-        .map_err(|e| {
-            std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Failed to run cargo new: {}", e)
-            )
-        })?;
-
-    if !status.success() {
-        return Err(
-            std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("cargo new {} failed", package_name)
-            )
-        );
-    }
-
-    println!("Created new project: {}", package_name);
-
-    // Overwrite Cargo.toml with correct dependencies
-    let cargo_toml_content = format!(
-        r#"[package]
-name = "{package_name}"
-version = "0.1.0"
-edition = "2024"
-
-[dependencies]
-progress_timer = {{ git = "https://github.com/ninouGx/progress_timer" }}
-aoc_utils = {{ path = "../aoc_utils" }}
-"#
-    );
-
-    let cargo_toml_path = format!("./{}/Cargo.toml", package_name);
-    let mut file = File::create(&cargo_toml_path)?;
-    file.write_all(cargo_toml_content.as_bytes())?;
-    println!("Configured {}", cargo_toml_path);
-
-    // Delete the placeholder main.rs
-    let main_rs_path = format!("./{}/src/main.rs", package_name);
-    if Path::new(&main_rs_path).exists() {
-        fs::remove_file(&main_rs_path)?;
-    }
-
-    Ok(())
-}
-
+/// Creates the solution and input files of a day in `year_YYYY`, creating the year
+/// crate with the default dependencies if needed, see `YearProject`
 pub fn create_day_files(year: &str, day: u32) -> std::io::Result<()> {
-    let day = format!("{:02}", day);
-    let package_name = format!("year_{}", year);
-    let year_project_path = format!("./{}", package_name);
-
-    if !Path::new(&year_project_path).exists() {
-        create_year_project(year)?;
-    }
-
-    fs::create_dir_all(format!("{}/src/data/test", year_project_path))?;
-    fs::create_dir_all(format!("{}/src/bin", year_project_path))?;
-
-    let template_path = "templates/day_template.rs";
-    let template = fs
-        ::read_to_string(&template_path)
-        .map_err(|e| {
-            std::io::Error::new(
-                std::io::ErrorKind::NotFound,
-                format!("Template file not found at '{}': {}", template_path, e)
-            )
-        })?;
-
-    // Solution file
-    let rs_path = format!("{}/src/bin/day{}.rs", year_project_path, day);
-    if !Path::new(&rs_path).exists() {
-        let mut file = File::create(&rs_path)?;
-        file.write_all(template.as_bytes())?;
-        println!("Created {}", rs_path);
-    }
-
-    // Input files
-    let input_path = format!("{}/src/data/day{}_input.txt", year_project_path, day);
-    let test_input_path = format!("{}/src/data/test/day{}_test_input.txt", year_project_path, day);
-
-    if !Path::new(&input_path).exists() {
-        File::create(&input_path)?;
-        println!("Created {}", input_path);
-    }
-
-    if !Path::new(&test_input_path).exists() {
-        File::create(&test_input_path)?;
-        println!("Created {}", test_input_path);
-    }
-
-    Ok(())
+    YearProject::new(year).create_day(Path::new("."), day)
 }
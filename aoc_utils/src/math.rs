@@ -0,0 +1,322 @@
+use std::fmt::Debug;
+use std::ops::{ Add, Div, Mul, Neg, Rem, Sub };
+
+/// Primitive integer usable by the number-theory helpers, implemented for every
+/// width from `i8`/`u8` up to `i128`/`u128`.
+pub trait Integer: Copy +
+    Ord +
+    Debug +
+    Add<Output = Self> +
+    Sub<Output = Self> +
+    Mul<Output = Self> +
+    Div<Output = Self> +
+    Rem<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+    const TEN: Self;
+    const BITS: u32;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    /// Absolute value, the identity for unsigned types
+    fn abs_value(self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+    fn leading_zeros(self) -> u32;
+    fn is_negative(self) -> bool;
+}
+
+/// Signed `Integer`, needed wherever Bézout coefficients can go negative.
+pub trait SignedInteger: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+    ($($t:ty),*; $abs:expr, $is_negative:expr) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const TEN: Self = 10;
+                const BITS: u32 = <$t>::BITS;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn abs_value(self) -> Self {
+                    $abs(self)
+                }
+
+                fn rem_euclid(self, rhs: Self) -> Self {
+                    <$t>::rem_euclid(self, rhs)
+                }
+
+                fn leading_zeros(self) -> u32 {
+                    <$t>::leading_zeros(self)
+                }
+
+                fn is_negative(self) -> bool {
+                    $is_negative(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize; |n| n, |_| false);
+impl_integer!(i8, i16, i32, i64, i128, isize; |n: Self| n.abs(), |n: Self| n < 0);
+
+macro_rules! impl_signed_integer {
+    ($($t:ty),*) => {
+        $(impl SignedInteger for $t {})*
+    };
+}
+
+impl_signed_integer!(i8, i16, i32, i64, i128, isize);
+
+fn two<T: Integer>() -> T {
+    T::ONE + T::ONE
+}
+
+/// Greatest common divisor, always non-negative. `gcd(0, 0)` is 0.
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::gcd(-12, 18), 6);
+/// ```
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs_value(), b.abs_value());
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, always non-negative.
+///
+/// # Panics
+/// When the result overflows `T`, see `checked_lcm`.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm overflowed")
+}
+
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a.abs_value() / gcd(a, b)).checked_mul(b.abs_value())
+}
+
+/// Greatest common divisor of every value, 0 for an empty iterator
+pub fn gcd_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ZERO, gcd)
+}
+
+/// Least common multiple of every value, 1 for an empty iterator.
+///
+/// # Example
+/// ```
+/// // Ghosts looping every 3, 4 and 10 steps all line up after 60 steps
+/// assert_eq!(aoc_utils::lcm_all([3u64, 4, 10]), 60);
+/// ```
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ONE, lcm)
+}
+
+pub fn checked_lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values.into_iter().try_fold(T::ONE, checked_lcm)
+}
+
+/// Extended Euclid: returns `(g, x, y)` with `a * x + b * y = g = gcd(a, b)`.
+///
+/// # Example
+/// ```
+/// let (g, x, y) = aoc_utils::extended_gcd(240i64, 46);
+/// assert_eq!(g, 2);
+/// assert_eq!(240 * x + 46 * y, 2);
+/// ```
+pub fn extended_gcd<T: SignedInteger>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r.is_negative() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `(a + b) mod m` for `a` and `b` already reduced, without overflowing
+fn add_reduced<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= m - b { a - (m - b) } else { a + b }
+}
+
+/// `(a - b) mod m` for `a` and `b` already reduced
+fn sub_reduced<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= b { a - b } else { m - (b - a) }
+}
+
+/// `(a * b) mod m` in `[0, m)`, falling back to double-and-add when the product
+/// would overflow `T`, so it works for any modulus up to `T::MAX`.
+pub fn mod_mul<T: Integer>(a: T, b: T, m: T) -> T {
+    assert!(m > T::ZERO, "modulus must be positive");
+    let (mut a, mut b) = (a.rem_euclid(m), b.rem_euclid(m));
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    let mut result = T::ZERO;
+    while b > T::ZERO {
+        if b % two() == T::ONE {
+            result = add_reduced(result, a, m);
+        }
+        a = add_reduced(a, a, m);
+        b = b / two();
+    }
+    result
+}
+
+/// `base^exp mod m` by binary exponentiation, never overflows.
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::mod_pow(4u32, 13, 497), 445);
+/// let modulus = u128::MAX - 158;
+/// assert_eq!(aoc_utils::mod_pow(3u128, 200, modulus), 0x126e_9006_c5be_7538_844c_57f5_ee34_dff0);
+/// ```
+pub fn mod_pow<T: Integer>(base: T, exp: T, m: T) -> T {
+    assert!(!exp.is_negative(), "negative exponent");
+    let mut base = base.rem_euclid(m);
+    let mut exp = exp;
+    let mut result = T::ONE % m;
+    while exp > T::ZERO {
+        if exp % two() == T::ONE {
+            result = mod_mul(result, base, m);
+        }
+        base = mod_mul(base, base, m);
+        exp = exp / two();
+    }
+    result
+}
+
+/// Inverse of `a` modulo `m`, `None` when they are not coprime.
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::mod_inverse(3, 11), Some(4));
+/// assert_eq!(aoc_utils::mod_inverse(6, 9), None);
+/// ```
+pub fn mod_inverse<T: Integer>(a: T, m: T) -> Option<T> {
+    assert!(m > T::ZERO, "modulus must be positive");
+    // Euclid keeping only the coefficient of `a`, reduced mod m so it never goes negative
+    let (mut old_r, mut r) = (a.rem_euclid(m), m);
+    let (mut old_s, mut s) = (T::ONE % m, T::ZERO);
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, sub_reduced(old_s, mod_mul(q, s, m), m));
+    }
+    (old_r == T::ONE || m == T::ONE).then_some(old_s)
+}
+
+/// Combines `x ≡ residue (mod modulus)` congruences into a single `(residue, modulus)`,
+/// where the modulus is the lcm of all moduli. Moduli don't need to be coprime.
+///
+/// Returns `None` when the congruences contradict each other.
+///
+/// # Panics
+/// When the combined modulus overflows `T`, see `checked_crt`.
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// // Non-coprime moduli
+/// assert_eq!(aoc_utils::crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+/// assert_eq!(aoc_utils::crt(&[(1, 4), (2, 6)]), None);
+/// ```
+pub fn crt<T: Integer>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    crt_inner(congruences).expect("crt modulus overflowed")
+}
+
+/// Like `crt`, but also returns `None` when the combined modulus overflows `T`
+pub fn checked_crt<T: Integer>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    crt_inner(congruences).flatten()
+}
+
+/// Outer `None` on overflow, inner `None` when there is no solution
+fn crt_inner<T: Integer>(congruences: &[(T, T)]) -> Option<Option<(T, T)>> {
+    let mut combined = (T::ZERO, T::ONE);
+    for &(residue, modulus) in congruences {
+        assert!(modulus > T::ZERO, "modulus must be positive");
+        let (r1, m1) = combined;
+        let r2 = residue.rem_euclid(modulus);
+        let g = gcd(m1, modulus);
+        let diff = sub_reduced(r2, r1 % modulus, modulus);
+        if diff % g != T::ZERO {
+            return Some(None);
+        }
+        // r1 + k * m1 ≡ r2 (mod modulus)  <=>  k ≡ diff/g * (m1/g)^-1 (mod modulus/g)
+        let reduced_modulus = modulus / g;
+        let inverse = mod_inverse(m1 / g, reduced_modulus)?;
+        let k = mod_mul(diff / g, inverse, reduced_modulus);
+        let lcm = (m1 / g).checked_mul(modulus)?;
+        // k < modulus/g and r1 < m1, so the sum stays below lcm
+        combined = (r1.checked_add(k.checked_mul(m1)?)?, lcm);
+    }
+    Some(Some(combined))
+}
+
+/// Largest `r` with `r * r <= n`.
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::isqrt(99u32), 9);
+/// assert_eq!(aoc_utils::isqrt(u128::MAX), u64::MAX as u128);
+/// ```
+pub fn isqrt<T: Integer>(n: T) -> T {
+    assert!(!n.is_negative(), "square root of a negative number");
+    if n < two() {
+        return n;
+    }
+    // Newton's method from a power of two above the root, so it only decreases
+    let bit_length = T::BITS - n.leading_zeros();
+    let mut x = (0..bit_length.div_ceil(2)).fold(T::ONE, |x, _| x * two());
+    loop {
+        let y = (x + n / x) / two();
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Every positive divisor of `n`, sorted.
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::divisors(28u32), vec![1, 2, 4, 7, 14, 28]);
+/// ```
+pub fn divisors<T: Integer>(n: T) -> Vec<T> {
+    assert!(n > T::ZERO, "divisors of a non-positive number");
+    let mut small = Vec::new();
+    let mut large = Vec::new();
+    let mut i = T::ONE;
+    while i <= n / i {
+        if n % i == T::ZERO {
+            small.push(i);
+            if i != n / i {
+                large.push(n / i);
+            }
+        }
+        i = i + T::ONE;
+    }
+    small.extend(large.into_iter().rev());
+    small
+}
@@ -0,0 +1,140 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{ BuildHasher, Hash };
+use std::sync::RwLock;
+
+type MemoFn<'f, K, V> = dyn Fn(&mut dyn FnMut(K) -> V, K) -> V + 'f;
+type SharedMemoFn<'f, K, V> = dyn Fn(&dyn Fn(K) -> V, K) -> V + Send + Sync + 'f;
+
+/// Recursive function whose results are cached by argument. The function gets a
+/// `recurse` callback as first parameter and makes its recursive calls through it.
+///
+/// # Example
+/// ```
+/// let mut fib = aoc_utils::Memo::new(|fib, n: u64| {
+///     if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+/// });
+/// assert_eq!(fib.get(90), 2880067194370816120);
+/// ```
+pub struct Memo<'f, K, V> {
+    cache: HashMap<K, V>,
+    function: Box<MemoFn<'f, K, V>>,
+}
+
+impl<'f, K: Hash + Eq + Clone, V: Clone> Memo<'f, K, V> {
+    pub fn new(function: impl Fn(&mut dyn FnMut(K) -> V, K) -> V + 'f) -> Self {
+        Memo {
+            cache: HashMap::new(),
+            function: Box::new(function),
+        }
+    }
+
+    pub fn get(&mut self, key: K) -> V {
+        compute(&mut self.cache, self.function.as_ref(), key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn into_cache(self) -> HashMap<K, V> {
+        self.cache
+    }
+}
+
+fn compute<K: Hash + Eq + Clone, V: Clone>(
+    cache: &mut HashMap<K, V>,
+    function: &MemoFn<'_, K, V>,
+    key: K
+) -> V {
+    if let Some(value) = cache.get(&key) {
+        return value.clone();
+    }
+    let value = function(&mut |k| compute(cache, function, k), key.clone());
+    cache.insert(key, value.clone());
+    value
+}
+
+const SHARDS: usize = 16;
+
+/// Thread-safe `Memo` that can be shared by reference across rayon's parallel
+/// iterators. The cache is split in shards so that threads rarely wait on each other.
+///
+/// No lock is held while computing, so two threads asking for the same missing key at
+/// the same time may both compute it.
+pub struct SharedMemo<'f, K, V> {
+    shards: Vec<RwLock<HashMap<K, V>>>,
+    hasher: RandomState,
+    function: Box<SharedMemoFn<'f, K, V>>,
+}
+
+impl<'f, K: Hash + Eq + Clone, V: Clone> SharedMemo<'f, K, V> {
+    pub fn new(function: impl Fn(&dyn Fn(K) -> V, K) -> V + Send + Sync + 'f) -> Self {
+        SharedMemo {
+            shards: (0..SHARDS).map(|_| RwLock::new(HashMap::new())).collect(),
+            hasher: RandomState::new(),
+            function: Box::new(function),
+        }
+    }
+
+    fn shard(&self, key: &K) -> &RwLock<HashMap<K, V>> {
+        &self.shards[(self.hasher.hash_one(key) as usize) % SHARDS]
+    }
+
+    pub fn get(&self, key: K) -> V {
+        if let Some(value) = self.shard(&key).read().unwrap().get(&key) {
+            return value.clone();
+        }
+        let value = (self.function)(&|k| self.get(k), key.clone());
+        self.shard(&key).write().unwrap().insert(key, value.clone());
+        value
+    }
+
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.read().unwrap().len())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rayon::prelude::*;
+
+    /// Ways to climb `n` steps taking 1, 2 or 3 at a time, without a cache
+    fn climbs(n: u64) -> u64 {
+        (1..=3)
+            .filter(|&step| step <= n)
+            .map(|step| if step == n { 1 } else { climbs(n - step) })
+            .sum()
+    }
+
+    #[test]
+    fn shared_memo_across_rayon_threads() {
+        let memo = SharedMemo::new(|climbs, n: u64| {
+            (1..=3)
+                .filter(|&step| step <= n)
+                .map(|step| if step == n { 1 } else { climbs(n - step) })
+                .sum::<u64>()
+        });
+        let parallel: Vec<u64> = (1..=60u64).into_par_iter().map(|n| memo.get(n)).collect();
+        let expected: Vec<u64> = (1..=20).map(climbs).collect();
+        assert_eq!(parallel[..20], expected[..]);
+        assert_eq!(parallel[59], 4_680_045_560_037_375);
+        assert_eq!(memo.len(), 60);
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+use std::fmt::{ self, Display };
+use std::str::FromStr;
+
+/// Parsing failure located in the input. Lines and columns start at 1; helpers that
+/// work on a single line report line 1 and `parse_lines` moves it to the real line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        ParseError { line, column, message: message.into() }
+    }
+
+    /// Error at byte offset `offset` of a single line
+    fn at(offset: usize, message: impl Into<String>) -> Self {
+        ParseError::new(1, offset + 1, message)
+    }
+
+    /// Moves the error `lines` lines further down
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /// Moves the error `columns` columns to the right, for a part of a line parsed on
+    /// its own that starts `columns` bytes into the line
+    ///
+    /// # Example
+    /// ```
+    /// let line = "a=[1,x]";
+    /// let error = aoc_utils::bracketed::<u8>(&line[2..]).unwrap_err().offset_columns(2);
+    /// assert_eq!((error.line, error.column), (1, 6));
+    /// ```
+    pub fn offset_columns(mut self, columns: usize) -> Self {
+        self.column += columns;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Byte offset of `part` inside `whole`, `part` must be a subslice of `whole`
+fn offset_in(whole: &str, part: &str) -> usize {
+    (part.as_ptr() as usize).saturating_sub(whole.as_ptr() as usize)
+}
+
+/// Parses `part`, a subslice of `line` such as a key from `key_values`, reporting
+/// failures at its column in `line`
+///
+/// # Example
+/// ```
+/// let line = "19x: 10 19";
+/// let (key, _) = aoc_utils::key_values::<u64>(line).unwrap();
+/// let error = aoc_utils::parse_within::<u64>(line, key).unwrap_err();
+/// assert_eq!((error.line, error.column), (1, 1));
+/// let error = aoc_utils::parse_within::<u64>(line, &line[1..3]).unwrap_err();
+/// assert_eq!((error.line, error.column), (1, 2));
+/// ```
+pub fn parse_within<T: FromStr>(line: &str, part: &str) -> Result<T, ParseError>
+    where T::Err: Display
+{
+    part.parse().map_err(|e| ParseError::at(offset_in(line, part), format!("'{}': {}", part, e)))
+}
+
+/// Parses a value surrounded by optional whitespace
+///
+/// # Example
+/// ```
+/// let error = aoc_utils::parse_value::<u8>(" 300").unwrap_err();
+/// assert_eq!((error.line, error.column), (1, 2));
+/// assert_eq!(error.message, "'300': number too large to fit in target type");
+/// ```
+pub fn parse_value<T: FromStr>(s: &str) -> Result<T, ParseError> where T::Err: Display {
+    parse_within(s, s.trim())
+}
+
+/// Every integer of `line`, a `-` right before digits being its sign unless it follows
+/// a letter or digit, so `3-5` gives 3 and 5 while `x=-5` gives -5.
+///
+/// # Example
+/// ```
+/// let values: Vec<i64> = aoc_utils::ints("p=0,-4 v=3,-3 range 10-20").unwrap();
+/// assert_eq!(values, vec![0, -4, 3, -3, 10, 20]);
+/// ```
+pub fn ints<T: FromStr>(line: &str) -> Result<Vec<T>, ParseError> where T::Err: Display {
+    let bytes = line.as_bytes();
+    let mut values = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let is_sign =
+            bytes[i] == b'-' &&
+            bytes.get(i + 1).is_some_and(u8::is_ascii_digit) &&
+            (i == 0 || !bytes[i - 1].is_ascii_alphanumeric());
+        if !is_sign && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        values.push(parse_within(line, &line[start..i])?);
+    }
+    Ok(values)
+}
+
+/// Applies `parse` to every line, fixing the line number of its errors
+///
+/// # Example
+/// ```
+/// let error = aoc_utils::parse_lines("1\n2\nx", aoc_utils::parse_value::<u32>).unwrap_err();
+/// assert_eq!((error.line, error.column), (3, 1));
+/// ```
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    parse: impl FnMut(&'a str) -> Result<T, ParseError>
+) -> Result<Vec<T>, ParseError> {
+    parse_lines_from(input, 0, parse)
+}
+
+fn parse_lines_from<'a, T>(
+    input: &'a str,
+    first_line: usize,
+    mut parse: impl FnMut(&'a str) -> Result<T, ParseError>
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|e| e.offset_lines(first_line + index)))
+        .collect()
+}
+
+/// Block of lines between blank lines, remembering where it starts in the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// Number of lines before this section, 0 for the first one
+    pub first_line: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Section<'a> {
+    pub fn lines(&self) -> std::str::Lines<'a> {
+        self.text.lines()
+    }
+
+    /// `parse_lines` with line numbers counted from the start of the whole input
+    pub fn parse_lines<T>(
+        &self,
+        parse: impl FnMut(&'a str) -> Result<T, ParseError>
+    ) -> Result<Vec<T>, ParseError> {
+        parse_lines_from(self.text, self.first_line, parse)
+    }
+}
+
+/// Splits the input on blank lines, ignoring leading, trailing and repeated blank lines.
+///
+/// # Example
+/// ```
+/// let input = "47|53\n97|13\n\n75,47,61\n";
+/// let sections = aoc_utils::sections(input);
+/// assert_eq!(sections.len(), 2);
+/// assert_eq!(sections[1].text, "75,47,61");
+/// assert_eq!(sections[1].first_line, 3);
+/// ```
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    // First line and byte offset of the section being read
+    let mut start: Option<(usize, usize)> = None;
+    let mut end = 0;
+    let mut offset = 0;
+    for (index, raw_line) in input.split_inclusive('\n').enumerate() {
+        if raw_line.trim().is_empty() {
+            if let Some((first_line, begin)) = start.take() {
+                sections.push(Section { first_line, text: &input[begin..end] });
+            }
+        } else {
+            start.get_or_insert((index, offset));
+            end = offset + raw_line.trim_end_matches(['\n', '\r']).len();
+        }
+        offset += raw_line.len();
+    }
+    if let Some((first_line, begin)) = start {
+        sections.push(Section { first_line, text: &input[begin..end] });
+    }
+    sections
+}
+
+/// Parses an `a-b` range, where both bounds may be negative.
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::range::<i32>("-5--2"), Ok((-5, -2)));
+/// assert_eq!(aoc_utils::range::<u64>(" 11-22"), Ok((11, 22)));
+/// ```
+pub fn range<T: FromStr>(s: &str) -> Result<(T, T), ParseError> where T::Err: Display {
+    let trimmed = s.trim();
+    let separator = trimmed
+        .char_indices()
+        .skip(1)
+        .find(|&(_, c)| c == '-')
+        .map(|(i, _)| i)
+        .ok_or_else(|| ParseError::at(offset_in(s, trimmed), "expected a range 'a-b'"))?;
+    let start = parse_within(s, &trimmed[..separator])?;
+    let end = parse_within(s, &trimmed[separator + 1..])?;
+    Ok((start, end))
+}
+
+/// Items of a list wrapped in `()`, `[]`, `{}` or `<>` and separated by commas.
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::bracketed::<u8>("(1,3)"), Ok(vec![1, 3]));
+/// assert_eq!(aoc_utils::bracketed::<u8>("[ 4, 7 ]"), Ok(vec![4, 7]));
+/// assert_eq!(aoc_utils::bracketed::<u8>("{}"), Ok(vec![]));
+/// assert_eq!(aoc_utils::bracketed::<u8>("(1,3").unwrap_err().column, 4);
+/// ```
+pub fn bracketed<T: FromStr>(s: &str) -> Result<Vec<T>, ParseError> where T::Err: Display {
+    let trimmed = s.trim();
+    let start = offset_in(s, trimmed);
+    let close = match trimmed.chars().next() {
+        Some('(') => ')',
+        Some('[') => ']',
+        Some('{') => '}',
+        Some('<') => '>',
+        _ => {
+            return Err(ParseError::at(start, "expected an opening bracket"));
+        }
+    };
+    let inner = trimmed[1..]
+        .strip_suffix(close)
+        .ok_or_else(|| ParseError::at(start + trimmed.len() - 1, format!("expected '{}'", close)))?;
+    if inner.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    inner
+        .split(',')
+        .map(|item| parse_within(s, item.trim()))
+        .collect()
+}
+
+/// Splits a `key: v1 v2` line into its key and its values, which can be separated by
+/// spaces or commas.
+///
+/// # Example
+/// ```
+/// let (key, values) = aoc_utils::key_values::<u64>("190: 10 19").unwrap();
+/// assert_eq!((key, values), ("190", vec![10, 19]));
+/// ```
+pub fn key_values<T: FromStr>(line: &str) -> Result<(&str, Vec<T>), ParseError>
+    where T::Err: Display
+{
+    let (key, rest) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::at(0, "expected 'key: values'"))?;
+    let values = rest
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|value| !value.is_empty())
+        .map(|value| parse_within(line, value))
+        .collect::<Result<_, _>>()?;
+    Ok((key.trim(), values))
+}
+
+/// Every `key: v1 v2` line of the input, by key
+///
+/// # Example
+/// ```
+/// let map = aoc_utils::key_value_map::<String>("you: bbb ccc\nbbb: out").unwrap();
+/// assert_eq!(map["you"], vec!["bbb", "ccc"]);
+/// ```
+pub fn key_value_map<T: FromStr>(input: &str) -> Result<HashMap<&str, Vec<T>>, ParseError>
+    where T::Err: Display
+{
+    parse_lines(input, key_values).map(|entries| entries.into_iter().collect())
+}
+
+/// Fields captured by `scan`, read in order with `parse_next`
+#[derive(Debug, Clone)]
+pub struct Fields<'a> {
+    line: &'a str,
+    fields: Vec<&'a str>,
+    next: usize,
+}
+
+impl<'a> Fields<'a> {
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'a str> {
+        self.fields.get(index).copied()
+    }
+
+    /// Parses the next field, failing at the end of the line when there is none left
+    pub fn parse_next<T: FromStr>(&mut self) -> Result<T, ParseError> where T::Err: Display {
+        let field = self.fields
+            .get(self.next)
+            .ok_or_else(|| ParseError::at(self.line.len(), "no field left"))?;
+        self.next += 1;
+        parse_within(self.line, field)
+    }
+}
+
+/// Matches `line` against a template where every `{}` captures text up to the next
+/// literal part of the template, or to the end of the line.
+///
+/// # Example
+/// ```
+/// let mut fields = aoc_utils::scan("{}-{}: {}", "1-3: abcde").unwrap();
+/// let low: u32 = fields.parse_next().unwrap();
+/// let high: u32 = fields.parse_next().unwrap();
+/// let password: String = fields.parse_next().unwrap();
+/// assert_eq!((low, high, password.as_str()), (1, 3, "abcde"));
+///
+/// let error = aoc_utils::scan("{}-{}: {}", "1-3 abcde").unwrap_err();
+/// assert_eq!(error.to_string(), "line 1, column 3: expected ': '");
+/// ```
+pub fn scan<'a>(template: &str, line: &'a str) -> Result<Fields<'a>, ParseError> {
+    let mut literals = template.split("{}");
+    let mut rest = line;
+    let prefix = literals.next().unwrap_or("");
+    rest = rest
+        .strip_prefix(prefix)
+        .ok_or_else(|| ParseError::at(0, format!("expected '{}'", prefix)))?;
+    let mut fields = Vec::new();
+    for literal in literals {
+        let end = if literal.is_empty() {
+            rest.len()
+        } else {
+            rest.find(literal).ok_or_else(|| {
+                ParseError::at(offset_in(line, rest), format!("expected '{}'", literal))
+            })?
+        };
+        fields.push(&rest[..end]);
+        rest = &rest[end + literal.len()..];
+    }
+    if !rest.is_empty() {
+        return Err(ParseError::at(offset_in(line, rest), "unexpected trailing text"));
+    }
+    Ok(Fields { line, fields, next: 0 })
+}
@@ -0,0 +1,369 @@
+use std::fmt::Write;
+use std::fs;
+use std::iter::Peekable;
+use std::str::Chars;
+use std::time::Instant;
+
+/// Escape sequence written before each recorded frame
+pub const CLEAR_SCREEN: &str = "\x1B[2J\x1B[1;1H";
+
+/// Terminal output written `time` seconds after the start of the recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub time: f64,
+    pub output: String,
+}
+
+impl Frame {
+    /// Whether the frame redraws the whole screen, so it can be shown on its own
+    pub fn clears_screen(&self) -> bool {
+        self.output.contains("\x1B[2J")
+    }
+}
+
+/// Timed terminal output, read from and written to asciinema v2 `.cast` files.
+///
+/// # Example
+/// ```
+/// use aoc_utils::{ Recorder, Recording };
+/// let mut recorder = Recorder::with_delay(250);
+/// recorder.frame("Step 0\n.#\n");
+/// recorder.frame("Step 1\n#.\n");
+/// let recording = recorder.finish();
+/// assert_eq!((recording.width, recording.height), (6, 2));
+///
+/// let cast = recording.to_cast();
+/// assert!(cast.starts_with("{\"version\": 2, \"width\": 6, \"height\": 2"));
+/// assert!(cast.ends_with("[0.250000, \"o\", \"\\u001b[2J\\u001b[1;1HStep 1\\r\\n#.\\r\\n\"]\n"));
+/// assert_eq!(Recording::from_cast(&cast).unwrap(), recording);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Recording {
+    pub width: usize,
+    pub height: usize,
+    pub frames: Vec<Frame>,
+}
+
+impl Recording {
+    /// Time of the last frame
+    pub fn duration(&self) -> f64 {
+        self.frames.last().map_or(0.0, |frame| frame.time)
+    }
+
+    /// Index of the frame on screen at `time`, the first one before it starts
+    pub fn frame_at(&self, time: f64) -> usize {
+        self.frames
+            .partition_point(|frame| frame.time <= time)
+            .saturating_sub(1)
+    }
+
+    /// Output that draws frame `index` from scratch: every frame since the last one
+    /// that cleared the screen
+    pub fn screen_at(&self, index: usize) -> String {
+        let start = self.frames[..=index]
+            .iter()
+            .rposition(Frame::clears_screen)
+            .unwrap_or(0);
+        self.frames[start..=index]
+            .iter()
+            .map(|frame| frame.output.as_str())
+            .collect()
+    }
+
+    pub fn to_cast(&self) -> String {
+        let mut cast = format!(
+            "{{\"version\": 2, \"width\": {}, \"height\": {}}}\n",
+            self.width,
+            self.height
+        );
+        for frame in &self.frames {
+            cast.push_str(&format!("[{:.6}, \"o\", ", frame.time));
+            write_json_string(&mut cast, &frame.output);
+            cast.push_str("]\n");
+        }
+        cast
+    }
+
+    /// Reads the header size and the output events of a cast file, other event
+    /// types being skipped.
+    pub fn from_cast(cast: &str) -> Result<Self, String> {
+        let mut lines = cast.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+        let (_, header) = lines.next().ok_or("Empty cast file")?;
+        let mut recording = Recording::default();
+        let mut json = JsonCursor::new(header);
+        json.object(|json, key| {
+            match key.as_str() {
+                "version" => {
+                    let version = json.number()?;
+                    if version != 2.0 {
+                        return Err(format!("Unsupported asciicast version {}", version));
+                    }
+                }
+                "width" => {
+                    recording.width = json.number()? as usize;
+                }
+                "height" => {
+                    recording.height = json.number()? as usize;
+                }
+                _ => json.skip_value()?,
+            }
+            Ok(())
+        }).map_err(|e| format!("line 1: {}", e))?;
+
+        for (i, line) in lines {
+            let mut json = JsonCursor::new(line);
+            let event = (|| {
+                json.expect('[')?;
+                let time = json.number()?;
+                json.expect(',')?;
+                let kind = json.string()?;
+                json.expect(',')?;
+                let data = json.string()?;
+                json.expect(']')?;
+                Ok::<_, String>((time, kind, data))
+            })().map_err(|e| format!("line {}: {}", i + 1, e))?;
+            let (time, kind, output) = event;
+            if kind == "o" {
+                recording.frames.push(Frame { time, output });
+            }
+        }
+        Ok(recording)
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        fs::write(path, self.to_cast())
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let cast = fs
+            ::read_to_string(path)
+            .map_err(|e| format!("Unable to read {}: {}", path, e))?;
+        Recording::from_cast(&cast)
+    }
+}
+
+/// Captures frames instead of drawing them, so an animation costs nothing while solving
+/// and can be replayed afterwards with the `replay` binary.
+pub struct Recorder {
+    start: Instant,
+    delay: Option<f64>,
+    recording: Recording,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Recorder {
+            start: Instant::now(),
+            delay: None,
+            recording: Recording::default(),
+        }
+    }
+}
+
+impl Recorder {
+    /// Timestamps frames with the wall clock time since the recorder was created
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Timestamps frames `delay_ms` apart, whatever the time spent between them
+    pub fn with_delay(delay_ms: u64) -> Self {
+        Recorder {
+            delay: Some((delay_ms as f64) / 1000.0),
+            ..Self::default()
+        }
+    }
+
+    /// Records a full screen of text, lines separated by `\n`
+    pub fn frame(&mut self, screen: &str) {
+        let time = match self.delay {
+            Some(delay) => delay * (self.recording.frames.len() as f64),
+            None => self.start.elapsed().as_secs_f64(),
+        };
+        let lines: Vec<&str> = screen.lines().collect();
+        let width = lines
+            .iter()
+            .map(|line| visible_width(line))
+            .max()
+            .unwrap_or(0);
+        self.recording.width = self.recording.width.max(width);
+        self.recording.height = self.recording.height.max(lines.len());
+
+        let mut output = String::from(CLEAR_SCREEN);
+        output.push_str(&screen.replace('\n', "\r\n"));
+        self.recording.frames.push(Frame { time, output });
+    }
+
+    pub fn len(&self) -> usize {
+        self.recording.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recording.frames.is_empty()
+    }
+
+    pub fn finish(self) -> Recording {
+        self.recording
+    }
+}
+
+/// Number of characters shown on screen, ANSI escape sequences taking no room
+fn visible_width(line: &str) -> usize {
+    let mut width = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1B' {
+            // Skip up to the final byte of the sequence
+            chars.find(|c| c.is_ascii_alphabetic());
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+fn write_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                write!(out, "\\u{:04x}", c as u32).unwrap();
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Just enough of a JSON reader for asciicast headers and events
+struct JsonCursor<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> JsonCursor<'a> {
+    fn new(text: &'a str) -> Self {
+        JsonCursor { chars: text.chars().peekable() }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+        self.chars.peek().copied()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.chars.next();
+                Ok(())
+            }
+            Some(c) => Err(format!("expected '{}', found '{}'", expected, c)),
+            None => Err(format!("expected '{}', found end of line", expected)),
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        self.peek();
+        let mut text = String::new();
+        while let Some(c) = self.chars.next_if(|c| "+-.eE".contains(*c) || c.is_ascii_digit()) {
+            text.push(c);
+        }
+        text.parse().map_err(|_| format!("invalid number '{}'", text))
+    }
+
+    fn hex_escape(&mut self) -> Result<u32, String> {
+        let hex: String = self.chars.by_ref().take(4).collect();
+        u32::from_str_radix(&hex, 16).map_err(|_| format!("invalid escape '\\u{}'", hex))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            match self.chars.next().ok_or("unterminated string")? {
+                '"' => return Ok(s),
+                '\\' => {
+                    let c = match self.chars.next().ok_or("unterminated string")? {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'u' => {
+                            let mut code = self.hex_escape()?;
+                            // Characters outside the BMP come as a surrogate pair
+                            if (0xd800..0xdc00).contains(&code) {
+                                self.expect('\\')?;
+                                self.expect('u')?;
+                                let low = self.hex_escape()?;
+                                if !(0xdc00..0xe000).contains(&low) {
+                                    return Err(format!("invalid low surrogate '\\u{:04x}'", low));
+                                }
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                            }
+                            char::from_u32(code).ok_or("invalid unicode escape")?
+                        }
+                        c => c,
+                    };
+                    s.push(c);
+                }
+                c => s.push(c),
+            }
+        }
+    }
+
+    /// Calls `field` on each key, which must consume the value
+    fn object(
+        &mut self,
+        mut field: impl FnMut(&mut Self, String) -> Result<(), String>
+    ) -> Result<(), String> {
+        self.expect('{')?;
+        if self.peek() == Some('}') {
+            self.chars.next();
+            return Ok(());
+        }
+        loop {
+            let key = self.string()?;
+            self.expect(':')?;
+            field(self, key)?;
+            match self.peek() {
+                Some(',') => {
+                    self.chars.next();
+                }
+                _ => return self.expect('}'),
+            }
+        }
+    }
+
+    fn skip_value(&mut self) -> Result<(), String> {
+        match self.peek() {
+            Some('"') => self.string().map(|_| ()),
+            Some('{') => self.object(|json, _| json.skip_value()),
+            Some('[') => {
+                self.chars.next();
+                if self.peek() == Some(']') {
+                    self.chars.next();
+                    return Ok(());
+                }
+                loop {
+                    self.skip_value()?;
+                    match self.peek() {
+                        Some(',') => {
+                            self.chars.next();
+                        }
+                        _ => return self.expect(']'),
+                    }
+                }
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                while self.chars.next_if(|c| c.is_ascii_alphabetic()).is_some() {}
+                Ok(())
+            }
+            _ => self.number().map(|_| ()),
+        }
+    }
+}
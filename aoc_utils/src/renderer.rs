@@ -0,0 +1,374 @@
+use std::collections::HashSet;
+use std::fmt::{ self, Write };
+use std::io::IsTerminal;
+use std::time::Duration;
+use crate::{ clear_screen_and_move_cursor, Image, Position, Recorder, Viewport };
+
+/// Terminal color, either from the 256 color palette or 24-bit RGB
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Ansi256(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Red, green and blue components, palette colors using the xterm defaults
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        const SYSTEM: [(u8, u8, u8); 16] = [
+            (0, 0, 0),
+            (128, 0, 0),
+            (0, 128, 0),
+            (128, 128, 0),
+            (0, 0, 128),
+            (128, 0, 128),
+            (0, 128, 128),
+            (192, 192, 192),
+            (128, 128, 128),
+            (255, 0, 0),
+            (0, 255, 0),
+            (255, 255, 0),
+            (0, 0, 255),
+            (255, 0, 255),
+            (0, 255, 255),
+            (255, 255, 255),
+        ];
+        const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        match *self {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Ansi256(index @ 0..=15) => SYSTEM[index as usize],
+            Color::Ansi256(index @ 16..=231) => {
+                let index = (index - 16) as usize;
+                (CUBE_LEVELS[index / 36], CUBE_LEVELS[(index / 6) % 6], CUBE_LEVELS[index % 6])
+            }
+            Color::Ansi256(index) => {
+                let level = 8 + 10 * (index - 232);
+                (level, level, level)
+            }
+        }
+    }
+
+    /// `#rrggbb` notation used by SVG and Graphviz
+    pub fn hex(&self) -> String {
+        let (r, g, b) = self.rgb();
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+
+    /// SGR parameters for this color, `layer` being 38 for foreground and 48 for background
+    fn sgr(&self, layer: u8) -> String {
+        match self {
+            Color::Ansi256(index) => format!("{};5;{}", layer, index),
+            Color::Rgb(r, g, b) => format!("{};2;{};{};{}", layer, r, g, b),
+        }
+    }
+}
+
+/// Whether the renderer emits ANSI color codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// Colors only when stdout is a terminal and `NO_COLOR` is not set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    pub(crate) fn enabled(&self) -> bool {
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => {
+                std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+            }
+        }
+    }
+}
+
+/// Named set of positions drawn with one glyph and optional colors
+#[derive(Debug, Clone)]
+pub struct Layer {
+    name: String,
+    glyph: char,
+    z: i32,
+    fg: Option<Color>,
+    bg: Option<Color>,
+    positions: HashSet<Position>,
+}
+
+impl Layer {
+    pub fn new(
+        name: impl Into<String>,
+        glyph: char,
+        positions: impl IntoIterator<Item = Position>
+    ) -> Self {
+        Layer {
+            name: name.into(),
+            glyph,
+            z: 0,
+            fg: None,
+            bg: None,
+            positions: positions.into_iter().collect(),
+        }
+    }
+
+    /// Priority of the layer, the highest one being drawn where layers overlap
+    pub fn z(mut self, z: i32) -> Self {
+        self.z = z;
+        self
+    }
+
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    /// Color used in images
+    fn color(&self) -> Color {
+        const PALETTE: [Color; 6] = [
+            Color::Rgb(255, 255, 255),
+            Color::Rgb(255, 140, 0),
+            Color::Rgb(80, 200, 255),
+            Color::Rgb(120, 220, 90),
+            Color::Rgb(230, 80, 80),
+            Color::Rgb(190, 120, 255),
+        ];
+        self.fg.or(self.bg).unwrap_or(PALETTE[(self.glyph as usize) % PALETTE.len()])
+    }
+
+    fn write_glyph<W: Write>(&self, out: &mut W, colored: bool) -> fmt::Result {
+        write_cell(out, self.glyph, self.fg, self.bg, colored)
+    }
+}
+
+fn write_cell<W: Write>(
+    out: &mut W,
+    glyph: char,
+    fg: Option<Color>,
+    bg: Option<Color>,
+    colored: bool
+) -> fmt::Result {
+    let codes: Vec<String> = [fg.map(|c| c.sgr(38)), bg.map(|c| c.sgr(48))]
+        .into_iter()
+        .flatten()
+        .collect();
+    if !colored || codes.is_empty() {
+        return out.write_char(glyph);
+    }
+    write!(out, "\x1B[{}m{}\x1B[0m", codes.join(";"), glyph)
+}
+
+/// Draws z-ordered layers of positions with optional colors, axis rulers and legend.
+/// Layers with the same z keep the order they were added in, the first one on top.
+///
+/// # Example
+/// ```
+/// use aoc_utils::{ Color, ColorMode, Layer, Position, Renderer };
+/// let out = Renderer::new()
+///     .layer(Layer::new("beam", '|', [Position::new(1, 0), Position::new(1, 1)]))
+///     .layer(Layer::new("splitter", '^', [Position::new(1, 1)]).z(1).fg(Color::Ansi256(208)))
+///     .color(ColorMode::Never)
+///     .legend(true)
+///     .render_to_string();
+/// assert_eq!(out, ".|\n.^\n^ splitter\n| beam\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Renderer {
+    layers: Vec<Layer>,
+    background: char,
+    background_color: Option<Color>,
+    viewport: Option<Viewport>,
+    legend: bool,
+    rulers: bool,
+    color: ColorMode,
+    zoom_out: usize,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Renderer {
+            layers: Vec::new(),
+            background: '.',
+            background_color: None,
+            viewport: None,
+            legend: false,
+            rulers: false,
+            color: ColorMode::Auto,
+            zoom_out: 1,
+        }
+    }
+}
+
+impl Renderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn layer(mut self, layer: Layer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Character of positions that are in no layer, `.` by default
+    pub fn background(mut self, glyph: char) -> Self {
+        self.background = glyph;
+        self
+    }
+
+    pub fn background_color(mut self, color: Color) -> Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// Area to draw, by default the smallest one holding every layer and the origin
+    pub fn viewport(mut self, viewport: Viewport) -> Self {
+        self.viewport = Some(viewport);
+        self
+    }
+
+    /// Lists each layer glyph with its name under the grid
+    pub fn legend(mut self, enabled: bool) -> Self {
+        self.legend = enabled;
+        self
+    }
+
+    /// Prints x coordinates above the grid, written vertically, and y coordinates on
+    /// the left of each row
+    pub fn rulers(mut self, enabled: bool) -> Self {
+        self.rulers = enabled;
+        self
+    }
+
+    pub fn color(mut self, mode: ColorMode) -> Self {
+        self.color = mode;
+        self
+    }
+
+    /// Layers from the top one down
+    fn sorted_layers(&self) -> Vec<&Layer> {
+        let mut layers: Vec<&Layer> = self.layers.iter().collect();
+        layers.sort_by_key(|layer| std::cmp::Reverse(layer.z));
+        layers
+    }
+
+    /// Draws each `factor`×`factor` block of positions as one character, showing the
+    /// top layer found in the block
+    pub fn zoom_out(mut self, factor: usize) -> Self {
+        self.zoom_out = factor.max(1);
+        self
+    }
+
+    /// Viewport set on the renderer, or the one around every layer and the origin
+    pub(crate) fn area(&self) -> Viewport {
+        self.viewport.unwrap_or_else(|| {
+            Viewport::around(self.layers.iter().flat_map(|layer| &layer.positions))
+        })
+    }
+
+    pub fn render<W: Write>(&self, out: &mut W) -> fmt::Result {
+        let colored = self.color.enabled();
+        let layers = self.sorted_layers();
+        let viewport = self.area();
+        let step = self.zoom_out;
+        let xs: Vec<i32> = (viewport.min.x..=viewport.max.x).step_by(step).collect();
+        let ys: Vec<i32> = (viewport.min.y..=viewport.max.y).step_by(step).collect();
+
+        let margin = if self.rulers {
+            ys.iter()
+                .map(|y| y.to_string().len())
+                .max()
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        if self.rulers {
+            let height = xs
+                .iter()
+                .map(|x| x.to_string().len())
+                .max()
+                .unwrap_or(0);
+            // Labels right-aligned so that the units digits share the last ruler row
+            let labels: Vec<Vec<char>> = xs
+                .iter()
+                .map(|x| format!("{:>height$}", x).chars().collect())
+                .collect();
+            for row in 0..height {
+                write!(out, "{:margin$} ", "")?;
+                for label in &labels {
+                    out.write_char(label[row])?;
+                }
+                writeln!(out)?;
+            }
+        }
+
+        for &y in &ys {
+            if self.rulers {
+                write!(out, "{:>margin$} ", y)?;
+            }
+            for &x in &xs {
+                let x_end = (x + step as i32).min(viewport.max.x + 1);
+                let y_end = (y + step as i32).min(viewport.max.y + 1);
+                let block: Vec<Position> = (y..y_end)
+                    .flat_map(|y| (x..x_end).map(move |x| Position { x, y }))
+                    .collect();
+                let top = layers
+                    .iter()
+                    .find(|layer| block.iter().any(|pos| layer.positions.contains(pos)));
+                match top {
+                    Some(layer) => layer.write_glyph(out, colored)?,
+                    None => write_cell(out, self.background, None, self.background_color, colored)?,
+                }
+            }
+            writeln!(out)?;
+        }
+
+        if self.legend {
+            for layer in &layers {
+                layer.write_glyph(out, colored)?;
+                writeln!(out, " {}", layer.name)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws each cell as a `scale`×`scale` square, ignoring legend and rulers. Layers
+    /// without colors get one from a fixed palette, the background defaulting to black.
+    pub fn image(&self, scale: usize) -> Image {
+        let layers = self.sorted_layers();
+        let background = self.background_color.unwrap_or(Color::Ansi256(0));
+        Image::from_cells(self.area(), scale, |pos| {
+            layers
+                .iter()
+                .find(|layer| layer.positions.contains(&pos))
+                .map_or(background, |layer| layer.color())
+        })
+    }
+
+    pub fn render_to_string(&self) -> String {
+        let mut out = String::new();
+        self.render(&mut out).expect("Writing to a String can't fail");
+        out
+    }
+
+    pub fn print(&self) {
+        print!("{}", self.render_to_string());
+    }
+
+    /// Clears the terminal, prints `title` and the frame, then waits `delay_ms`
+    pub fn print_frame(&self, title: &str, delay_ms: u64) {
+        clear_screen_and_move_cursor();
+        println!("{}", title);
+        self.print();
+        std::io::Write::flush(&mut std::io::stdout()).unwrap();
+        std::thread::sleep(Duration::from_millis(delay_ms));
+    }
+
+    /// Adds `title` and the frame to a recording instead of printing them
+    pub fn record_frame(&self, recorder: &mut Recorder, title: &str) {
+        recorder.frame(&format!("{}\n{}", title, self.render_to_string()));
+    }
+}
@@ -0,0 +1,362 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use crate::{ DayTemplate, Puzzle, DEFAULT_TEMPLATE };
+
+const DAYS_DECLARATION: &str = "pub const DAYS: &[u32] = &[";
+
+/// Byte ranges of the quoted strings of a TOML array body, quotes included, skipping
+/// comments. Both basic `"…"` and literal `'…'` strings are recognised.
+fn string_entries(body: &str) -> Vec<(usize, usize)> {
+    let mut entries = Vec::new();
+    let mut chars = body.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                let mut end = body.len();
+                while let Some((j, next)) = chars.next() {
+                    if next == c {
+                        end = j + 1;
+                        break;
+                    }
+                    // Only basic strings have escapes
+                    if next == '\\' && c == '"' {
+                        chars.next();
+                    }
+                }
+                entries.push((i, end));
+            }
+            '#' => {
+                chars.find(|&(_, c)| c == '\n');
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+/// Offset of the `]` closing an array whose body starts at `start`
+fn array_end(text: &str, start: usize) -> Option<usize> {
+    let body = &text[start..];
+    let mut quote = None;
+    let mut escaped = false;
+    let mut in_comment = false;
+    for (i, c) in body.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '\n' => in_comment = false,
+            None if in_comment => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '#' => in_comment = true,
+                ']' => {
+                    return Some(start + i);
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// Adds `member` to the `[workspace]` members of a root manifest, leaving the rest of
+/// the text untouched. The entry follows the layout and quotes of the list, inline or
+/// one per line, and nothing changes when the member is already listed.
+///
+/// # Example
+/// ```
+/// use aoc_utils::add_workspace_member;
+/// let manifest = "[workspace]\nmembers = [\"aoc_utils\", \"year_2024\"]\nresolver = \"2\"\n";
+/// let updated = add_workspace_member(manifest, "year_2026").unwrap();
+/// assert_eq!(
+///     updated,
+///     "[workspace]\nmembers = [\"aoc_utils\", \"year_2024\", \"year_2026\"]\nresolver = \"2\"\n"
+/// );
+/// assert_eq!(add_workspace_member(&updated, "year_2026").unwrap(), updated);
+///
+/// let manifest = "[workspace]\nmembers = [\n    \"aoc_utils\",\n]\n";
+/// assert_eq!(
+///     add_workspace_member(manifest, "year_2026").unwrap(),
+///     "[workspace]\nmembers = [\n    \"aoc_utils\",\n    \"year_2026\",\n]\n"
+/// );
+///
+/// let manifest = "[workspace]\nmembers = ['aoc_utils', 'year_2024']\n";
+/// assert_eq!(add_workspace_member(manifest, "year_2024").unwrap(), manifest);
+/// assert_eq!(
+///     add_workspace_member(manifest, "year_2026").unwrap(),
+///     "[workspace]\nmembers = ['aoc_utils', 'year_2024', 'year_2026']\n"
+/// );
+/// ```
+pub fn add_workspace_member(manifest: &str, member: &str) -> Result<String, String> {
+    let mut offset = 0;
+    let mut table = None;
+    let mut members = None;
+    for line in manifest.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') && !trimmed.starts_with("[[") && table.is_some() {
+            break;
+        }
+        if trimmed.starts_with("[workspace]") {
+            table = Some(offset + line.len());
+        } else if table.is_some() {
+            let key = trimmed.split('=').next().unwrap_or("").trim();
+            if key == "members" && trimmed.contains('=') {
+                members = Some(offset + line.find('=').unwrap());
+                break;
+            }
+        }
+        offset += line.len();
+    }
+
+    let Some(table_start) = table else {
+        return Err("No [workspace] table in the manifest".to_string());
+    };
+    let Some(equals) = members else {
+        let mut text = manifest.to_string();
+        let mut at = table_start;
+        if !text[..at].ends_with('\n') {
+            text.insert(at, '\n');
+            at += 1;
+        }
+        text.insert_str(at, &format!("members = [\"{}\"]\n", member));
+        return Ok(text);
+    };
+
+    let open = manifest[equals..]
+        .find('[')
+        .map(|i| equals + i + 1)
+        .ok_or("Workspace members are not an array")?;
+    let close = array_end(manifest, open).ok_or("Unclosed workspace members array")?;
+    let body = &manifest[open..close];
+    let entries = string_entries(body);
+    if entries.iter().any(|&(start, end)| body.get(start + 1..end - 1) == Some(member)) {
+        return Ok(manifest.to_string());
+    }
+    let quote = entries.last().map_or("\"", |&(start, _)| &body[start..start + 1]);
+    let entry = format!("{}{}{}", quote, member, quote);
+
+    let multiline = body.contains('\n');
+    let (at, insertion) = match entries.last() {
+        None if multiline => (0, format!("\n    {},", entry)),
+        None => (0, entry),
+        Some(&(start, end)) => {
+            let indent: String = body[..start]
+                .rsplit('\n')
+                .next()
+                .unwrap_or("")
+                .chars()
+                .take_while(|c| c.is_whitespace())
+                .collect();
+            let after = &body[end..];
+            let comma = after.len() - after.trim_start().len();
+            match (after.trim_start().starts_with(','), multiline) {
+                (true, true) => (end + comma + 1, format!("\n{}{},", indent, entry)),
+                (true, false) => (end + comma + 1, format!(" {}", entry)),
+                (false, true) => (end, format!(",\n{}{}", indent, entry)),
+                (false, false) => (end, format!(", {}", entry)),
+            }
+        }
+    };
+    let mut text = manifest.to_string();
+    text.insert_str(open + at, &insertion);
+    Ok(text)
+}
+
+/// Adds `day` to the sorted `DAYS` list of a year crate `lib.rs`, `None` when the file
+/// has no such list
+pub fn register_day(lib: &str, day: u32) -> Option<String> {
+    let start = lib.find(DAYS_DECLARATION)? + DAYS_DECLARATION.len();
+    let end = start + lib[start..].find(']')?;
+    let mut days: Vec<u32> = lib[start..end]
+        .split(',')
+        .filter_map(|n| n.trim().parse().ok())
+        .collect();
+    if !days.contains(&day) {
+        days.push(day);
+        days.sort_unstable();
+    }
+    let list: Vec<String> = days.iter().map(u32::to_string).collect();
+    Some(format!("{}{}{}", &lib[..start], list.join(", "), &lib[end..]))
+}
+
+/// Writes `content` to `path` unless the file already exists
+fn create_file(path: &Path, content: &str) -> io::Result<()> {
+    if !path.exists() {
+        fs::write(path, content)?;
+        println!("Created {}", path.display());
+    }
+    Ok(())
+}
+
+/// Crate holding the solutions of one year: a `year_YYYY` package with a binary per
+/// day and a `DAYS` registry in its `lib.rs`. Everything is created relative to a
+/// workspace root so that scaffolding can run anywhere.
+///
+/// # Example
+/// ```
+/// use aoc_utils::YearProject;
+/// let root = std::env::temp_dir().join(format!("aoc_scaffold_{}", std::process::id()));
+/// std::fs::create_dir_all(&root).unwrap();
+/// std::fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"aoc_utils\"]\n").unwrap();
+///
+/// let project = YearProject::new("2026").dependency("rayon", "1.11.0");
+/// project.create(&root).unwrap();
+/// project.create(&root).unwrap();
+///
+/// let workspace = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+/// assert_eq!(workspace, "[workspace]\nmembers = [\"aoc_utils\", \"year_2026\"]\n");
+/// let manifest = std::fs::read_to_string(root.join("year_2026/Cargo.toml")).unwrap();
+/// assert!(manifest.contains("rayon = \"1.11.0\""));
+/// assert!(root.join("year_2026/src/data/test").is_dir());
+/// std::fs::remove_dir_all(&root).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct YearProject {
+    year: String,
+    edition: String,
+    dependencies: Vec<(String, String)>,
+}
+
+impl YearProject {
+    /// Project on edition 2024 depending on `progress_timer` and `aoc_utils`
+    pub fn new(year: &str) -> Self {
+        YearProject {
+            year: year.to_string(),
+            edition: "2024".to_string(),
+            dependencies: vec![
+                (
+                    "progress_timer".to_string(),
+                    "{ git = \"https://github.com/ninouGx/progress_timer\" }".to_string(),
+                ),
+                ("aoc_utils".to_string(), "{ path = \"../aoc_utils\" }".to_string()),
+            ],
+        }
+    }
+
+    pub fn edition(mut self, edition: &str) -> Self {
+        self.edition = edition.to_string();
+        self
+    }
+
+    /// Adds or replaces a dependency. `spec` is either a version such as `1.11.0` or a
+    /// TOML value such as `{ path = "../lib" }`.
+    pub fn dependency(mut self, name: &str, spec: &str) -> Self {
+        let spec = if spec.starts_with('{') || spec.starts_with('"') {
+            spec.to_string()
+        } else {
+            format!("\"{}\"", spec)
+        };
+        match self.dependencies.iter_mut().find(|(existing, _)| existing == name) {
+            Some(dependency) => dependency.1 = spec,
+            None => self.dependencies.push((name.to_string(), spec)),
+        }
+        self
+    }
+
+    pub fn without_dependency(mut self, name: &str) -> Self {
+        self.dependencies.retain(|(existing, _)| existing != name);
+        self
+    }
+
+    pub fn package_name(&self) -> String {
+        format!("year_{}", self.year)
+    }
+
+    pub fn manifest(&self) -> String {
+        let mut manifest = format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"{}\"\n\n[dependencies]\n",
+            self.package_name(),
+            self.edition
+        );
+        for (name, spec) in &self.dependencies {
+            manifest.push_str(&format!("{} = {}\n", name, spec));
+        }
+        manifest
+    }
+
+    fn lib(&self) -> String {
+        format!(
+            "//! Advent of Code {} solutions, one binary per day in `src/bin`\n\n\
+             /// Days with a solution, kept up to date by `cargo new-day`\n\
+             {}];\n",
+            self.year,
+            DAYS_DECLARATION
+        )
+    }
+
+    /// Creates the crate under `root` and adds it to the workspace members of
+    /// `root/Cargo.toml`. Existing files are kept, so running it twice is harmless.
+    pub fn create(&self, root: &Path) -> io::Result<()> {
+        // The workspace is checked first so that a bad manifest leaves no crate behind
+        let workspace_path = root.join("Cargo.toml");
+        let workspace = fs::read_to_string(&workspace_path).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Workspace manifest not found at '{}': {}", workspace_path.display(), e)
+            )
+        })?;
+        let updated = add_workspace_member(&workspace, &self.package_name()).map_err(
+            io::Error::other
+        )?;
+
+        let project = root.join(self.package_name());
+        fs::create_dir_all(project.join("src/bin"))?;
+        fs::create_dir_all(project.join("src/data/test"))?;
+        create_file(&project.join("Cargo.toml"), &self.manifest())?;
+        create_file(&project.join("src/lib.rs"), &self.lib())?;
+        if updated != workspace {
+            fs::write(&workspace_path, updated)?;
+            println!("Added {} to the workspace members", self.package_name());
+        }
+        Ok(())
+    }
+
+    /// Creates the solution and input files of `day` from the default template, see
+    /// `create_puzzle`
+    pub fn create_day(&self, root: &Path, day: u32) -> io::Result<()> {
+        let template = DayTemplate::load(root, DEFAULT_TEMPLATE).map_err(|e| {
+            io::Error::new(io::ErrorKind::NotFound, e)
+        })?;
+        self.create_puzzle(root, &Puzzle::new(day), &template)
+    }
+
+    /// Creates the solution of the puzzle from `template` and its input files, the test
+    /// input holding the first example, along with the crate if needed, and registers
+    /// the day in `lib.rs`
+    pub fn create_puzzle(
+        &self,
+        root: &Path,
+        puzzle: &Puzzle,
+        template: &DayTemplate
+    ) -> io::Result<()> {
+        let project = root.join(self.package_name());
+        if !project.exists() {
+            self.create(root)?;
+        }
+        fs::create_dir_all(project.join("src/data/test"))?;
+        fs::create_dir_all(project.join("src/bin"))?;
+
+        let source = template.render(&self.year, puzzle).map_err(io::Error::other)?;
+        let test_input = puzzle.examples.first().map_or("", |example| example.input.as_str());
+        let day_padded = format!("{:02}", puzzle.day);
+        create_file(&project.join(format!("src/bin/day{}.rs", day_padded)), &source)?;
+        create_file(&project.join(format!("src/data/day{}_input.txt", day_padded)), "")?;
+        create_file(
+            &project.join(format!("src/data/test/day{}_test_input.txt", day_padded)),
+            test_input
+        )?;
+
+        // Crates created before the registry existed have no lib.rs
+        let lib_path = project.join("src/lib.rs");
+        if let Ok(lib) = fs::read_to_string(&lib_path) {
+            let updated = register_day(&lib, puzzle.day);
+            if let Some(updated) = updated.filter(|updated| *updated != lib) {
+                fs::write(&lib_path, updated)?;
+            }
+        }
+        Ok(())
+    }
+}
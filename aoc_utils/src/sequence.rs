@@ -0,0 +1,140 @@
+use std::collections::VecDeque;
+
+/// Keeps `k` items in order, dropping an item whenever a later one should come first
+/// according to `comes_first`, with a monotonic stack so the whole scan is O(n).
+fn best_subsequence<T: Clone>(
+    items: &[T],
+    k: usize,
+    comes_first: impl Fn(&T, &T) -> bool
+) -> Vec<T> {
+    assert!(k <= items.len(), "subsequence of {} items out of {}", k, items.len());
+    let mut to_drop = items.len() - k;
+    let mut stack: Vec<T> = Vec::with_capacity(items.len());
+    for item in items {
+        while to_drop > 0 && stack.last().is_some_and(|top| comes_first(item, top)) {
+            stack.pop();
+            to_drop -= 1;
+        }
+        stack.push(item.clone());
+    }
+    stack.truncate(k);
+    stack
+}
+
+/// Lexicographically largest subsequence of `k` items, keeping their order.
+///
+/// # Example
+/// ```
+/// // Largest 3 digit number made of digits picked left to right
+/// let digits = [8, 1, 1, 9, 2, 1, 5];
+/// assert_eq!(aoc_utils::max_subsequence(&digits, 3), vec![9, 2, 5]);
+/// ```
+pub fn max_subsequence<T: Ord + Clone>(items: &[T], k: usize) -> Vec<T> {
+    best_subsequence(items, k, |a, b| a > b)
+}
+
+/// Lexicographically smallest subsequence of `k` items, keeping their order.
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::min_subsequence(&[3, 5, 2, 6], 2), vec![2, 6]);
+/// ```
+pub fn min_subsequence<T: Ord + Clone>(items: &[T], k: usize) -> Vec<T> {
+    best_subsequence(items, k, |a, b| a < b)
+}
+
+/// Index of the first strictly greater item to the right of each item
+///
+/// # Example
+/// ```
+/// let next = aoc_utils::next_greater(&[2, 1, 2, 4, 3]);
+/// assert_eq!(next, vec![Some(3), Some(2), Some(3), None, None]);
+/// ```
+pub fn next_greater<T: Ord>(items: &[T]) -> Vec<Option<usize>> {
+    let mut next = vec![None; items.len()];
+    // Indices still waiting for a greater item, their values decreasing
+    let mut waiting: Vec<usize> = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        while let Some(&j) = waiting.last().filter(|&&j| items[j] < *item) {
+            next[j] = Some(i);
+            waiting.pop();
+        }
+        waiting.push(i);
+    }
+    next
+}
+
+/// Best item of every window of `window` consecutive items, a deque keeping the
+/// candidates so each item is pushed and popped once
+fn sliding_window_best<T: Clone>(
+    items: &[T],
+    window: usize,
+    beats: impl Fn(&T, &T) -> bool
+) -> Vec<T> {
+    assert!(window > 0, "empty sliding window");
+    let mut candidates: VecDeque<usize> = VecDeque::new();
+    let mut best = Vec::with_capacity(items.len().saturating_sub(window - 1));
+    for (i, item) in items.iter().enumerate() {
+        while candidates.back().is_some_and(|&j| !beats(&items[j], item)) {
+            candidates.pop_back();
+        }
+        candidates.push_back(i);
+        if candidates[0] + window <= i {
+            candidates.pop_front();
+        }
+        if i + 1 >= window {
+            best.push(items[candidates[0]].clone());
+        }
+    }
+    best
+}
+
+/// Maximum of every window of `window` consecutive items, in O(n).
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::sliding_window_max(&[1, 3, -1, -3, 5, 3], 3), vec![3, 3, 5, 5]);
+/// ```
+pub fn sliding_window_max<T: Ord + Clone>(items: &[T], window: usize) -> Vec<T> {
+    sliding_window_best(items, window, |a, b| a > b)
+}
+
+/// Minimum of every window of `window` consecutive items, in O(n).
+///
+/// # Example
+/// ```
+/// assert_eq!(aoc_utils::sliding_window_min(&[1, 3, -1, -3, 5, 3], 3), vec![-1, -3, -3, -3]);
+/// ```
+pub fn sliding_window_min<T: Ord + Clone>(items: &[T], window: usize) -> Vec<T> {
+    sliding_window_best(items, window, |a, b| a < b)
+}
+
+/// One of the longest strictly increasing subsequences, in O(n log n).
+///
+/// # Example
+/// ```
+/// let lis = aoc_utils::longest_increasing_subsequence(&[3, 1, 4, 1, 5, 9, 2, 6]);
+/// assert_eq!(lis, vec![1, 4, 5, 6]);
+/// ```
+pub fn longest_increasing_subsequence<T: Ord + Clone>(items: &[T]) -> Vec<T> {
+    // tails[len] is the index of the smallest item ending an increasing run of len + 1
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; items.len()];
+    for (i, item) in items.iter().enumerate() {
+        let len = tails.partition_point(|&j| items[j] < *item);
+        previous[i] = len.checked_sub(1).map(|l| tails[l]);
+        if len == tails.len() {
+            tails.push(i);
+        } else {
+            tails[len] = i;
+        }
+    }
+    let mut subsequence = Vec::with_capacity(tails.len());
+    let mut current = tails.last().copied();
+    while let Some(i) = current {
+        subsequence.push(items[i].clone());
+        current = previous[i];
+    }
+    subsequence.reverse();
+    subsequence
+}
@@ -0,0 +1,616 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{ Add, Div, Mul, Neg, Sub };
+use crate::math::gcd;
+
+/// Exact fraction, always stored reduced with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "Rational with a zero denominator");
+        let sign = if den < 0 { -1 } else { 1 };
+        let divisor = gcd(num, den).max(1);
+        Rational {
+            num: (sign * num) / divisor,
+            den: (sign * den) / divisor,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Rational { num: 0, den: 1 }
+    }
+
+    pub fn one() -> Self {
+        Rational { num: 1, den: 1 }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Rational { num: value as i128, den: 1 }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+    fn add(self, rhs: Self) -> Self::Output {
+        Rational::new(self.num * rhs.den + rhs.num * self.den, self.den * rhs.den)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Rational::new(self.num * rhs.num, self.den * rhs.den)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+    fn div(self, rhs: Self) -> Self::Output {
+        Rational::new(self.num * rhs.den, self.den * rhs.num)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+    fn neg(self) -> Self::Output {
+        Rational { num: -self.num, den: self.den }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// Linear system `A x = b` reduced to row echelon form with exact rational arithmetic.
+/// Variables without a pivot are free and can take any value.
+#[derive(Debug, Clone)]
+pub struct LinearSystem {
+    /// Reduced augmented rows, one per pivot, the last column being the right-hand side
+    rows: Vec<Vec<Rational>>,
+    pivots: Vec<usize>,
+    free: Vec<usize>,
+    consistent: bool,
+}
+
+impl LinearSystem {
+    /// Runs Gauss-Jordan elimination on `a` (one row per equation) and `b`.
+    pub fn new(a: &[Vec<i64>], b: &[i64]) -> Self {
+        let n_vars = a
+            .iter()
+            .map(|row| row.len())
+            .max()
+            .unwrap_or(0);
+        let mut rows: Vec<Vec<Rational>> = a
+            .iter()
+            .zip(b)
+            .map(|(row, &rhs)| {
+                (0..n_vars)
+                    .map(|j| Rational::from(row.get(j).copied().unwrap_or(0)))
+                    .chain(std::iter::once(Rational::from(rhs)))
+                    .collect()
+            })
+            .collect();
+
+        let mut pivots = Vec::new();
+        let mut free = Vec::new();
+        let mut pivot_row = 0;
+        for col in 0..n_vars {
+            let Some(found) = (pivot_row..rows.len()).find(|&r| !rows[r][col].is_zero()) else {
+                free.push(col);
+                continue;
+            };
+            rows.swap(pivot_row, found);
+            let pivot = rows[pivot_row][col];
+            for value in rows[pivot_row].iter_mut() {
+                *value = *value / pivot;
+            }
+            let pivot_values = rows[pivot_row].clone();
+            for (r, row) in rows.iter_mut().enumerate() {
+                let factor = row[col];
+                if r != pivot_row && !factor.is_zero() {
+                    for (value, &p) in row.iter_mut().zip(&pivot_values).skip(col) {
+                        *value = *value - factor * p;
+                    }
+                }
+            }
+            pivots.push(col);
+            pivot_row += 1;
+        }
+
+        // Leftover rows have no variable left, so a non-zero right-hand side is a contradiction
+        let consistent = rows[pivot_row..].iter().all(|row| row[n_vars].is_zero());
+        rows.truncate(pivot_row);
+        LinearSystem { rows, pivots, free, consistent }
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.consistent
+    }
+
+    pub fn pivot_variables(&self) -> &[usize] {
+        &self.pivots
+    }
+
+    pub fn free_variables(&self) -> &[usize] {
+        &self.free
+    }
+
+    fn n_vars(&self) -> usize {
+        self.pivots.len() + self.free.len()
+    }
+
+    /// Full solution once the free variables (in `free_variables` order) are fixed,
+    /// or `None` if the system has no solution.
+    pub fn solve_with(&self, free_values: &[Rational]) -> Option<Vec<Rational>> {
+        if !self.consistent || free_values.len() != self.free.len() {
+            return None;
+        }
+        let n_vars = self.n_vars();
+        let mut solution = vec![Rational::zero(); n_vars];
+        for (&var, &value) in self.free.iter().zip(free_values) {
+            solution[var] = value;
+        }
+        for (row, &pivot) in self.rows.iter().zip(&self.pivots) {
+            solution[pivot] = self.free
+                .iter()
+                .zip(free_values)
+                .fold(row[n_vars], |acc, (&var, &value)| acc - row[var] * value);
+        }
+        Some(solution)
+    }
+
+    /// The only solution, if the system has exactly one
+    pub fn unique_solution(&self) -> Option<Vec<Rational>> {
+        if !self.free.is_empty() {
+            return None;
+        }
+        self.solve_with(&[])
+    }
+
+    /// Non-negative integer solution minimizing the sum of all variables, each variable
+    /// being at most its entry in `upper_bounds`. Free variables are enumerated by
+    /// branch and bound, pruning on the best sum found so far.
+    pub fn min_sum_nonnegative(&self, upper_bounds: &[i64]) -> Option<Vec<i64>> {
+        if !self.consistent {
+            return None;
+        }
+        let n_vars = self.n_vars();
+        // Scale each row to integers: den * pivot = rhs - sum(coef * free)
+        let rows: Vec<ScaledRow> = self.rows
+            .iter()
+            .zip(&self.pivots)
+            .map(|(row, &pivot)| {
+                let den = row.iter().fold(1, |acc, v| (acc / gcd(acc, v.den)) * v.den);
+                let scale = |v: &Rational| (v.num * den) / v.den;
+                let coefs = self.free
+                    .iter()
+                    .map(|&var| scale(&row[var]))
+                    .collect();
+                ScaledRow {
+                    den,
+                    rhs: scale(&row[n_vars]),
+                    coefs,
+                    bound: upper_bounds.get(pivot).copied().unwrap_or(0) as i128,
+                }
+            })
+            .collect();
+        let bounds: Vec<i128> = self.free
+            .iter()
+            .map(|&var| upper_bounds.get(var).copied().unwrap_or(0) as i128)
+            .collect();
+
+        let mut search = FreeVariableSearch {
+            rows: &rows,
+            bounds: &bounds,
+            values: vec![0; self.free.len()],
+            best: None,
+        };
+        search.explore(0);
+
+        let (_, values) = search.best?;
+        let mut solution = vec![0; n_vars];
+        for (&var, &value) in self.free.iter().zip(&values) {
+            solution[var] = value as i64;
+        }
+        for (row, &pivot) in rows.iter().zip(&self.pivots) {
+            solution[pivot] = row.pivot_value(&values)? as i64;
+        }
+        Some(solution)
+    }
+}
+
+/// Pivot row multiplied by `den` so that `den * pivot = rhs - sum(coefs * free values)`
+struct ScaledRow {
+    den: i128,
+    rhs: i128,
+    coefs: Vec<i128>,
+    bound: i128,
+}
+
+impl ScaledRow {
+    /// Value of the pivot variable, if it is an integer within `[0, bound]`
+    fn pivot_value(&self, free_values: &[i128]) -> Option<i128> {
+        let total: i128 = self.coefs
+            .iter()
+            .zip(free_values)
+            .map(|(c, v)| c * v)
+            .sum();
+        let scaled = self.rhs - total;
+        if scaled < 0 || scaled % self.den != 0 || scaled / self.den > self.bound {
+            return None;
+        }
+        Some(scaled / self.den)
+    }
+}
+
+struct FreeVariableSearch<'a> {
+    rows: &'a [ScaledRow],
+    bounds: &'a [i128],
+    values: Vec<i128>,
+    best: Option<(i128, Vec<i128>)>,
+}
+
+impl FreeVariableSearch<'_> {
+    /// Evaluates the pivot variables once every free variable is set
+    fn pivot_sum(&self) -> Option<i128> {
+        self.rows
+            .iter()
+            .map(|row| row.pivot_value(&self.values))
+            .sum()
+    }
+
+    /// Whether the pivot rows can still be non-negative given the first `assigned` values
+    fn is_feasible(&self, assigned: usize) -> bool {
+        self.rows.iter().all(|row| {
+            let best_case: i128 = row.coefs
+                .iter()
+                .enumerate()
+                .map(|(j, &c)| {
+                    if j < assigned { c * self.values[j] } else { (c * self.bounds[j]).min(0) }
+                })
+                .sum();
+            row.rhs - best_case >= 0
+        })
+    }
+
+    fn explore(&mut self, index: usize) {
+        let partial: i128 = self.values[..index].iter().sum();
+        if self.best.as_ref().is_some_and(|(best, _)| partial >= *best) {
+            return;
+        }
+        if !self.is_feasible(index) {
+            return;
+        }
+        if index == self.values.len() {
+            if let Some(pivots) = self.pivot_sum() {
+                let total = partial + pivots;
+                if self.best.as_ref().is_none_or(|(best, _)| total < *best) {
+                    self.best = Some((total, self.values.clone()));
+                }
+            }
+            return;
+        }
+        for value in 0..=self.bounds[index] {
+            self.values[index] = value;
+            self.explore(index + 1);
+        }
+        self.values[index] = 0;
+    }
+}
+
+/// Minimizes the sum of non-negative integers `x` such that `A x = b`, for a matrix
+/// with non-negative entries (which bounds each variable by the equations it appears in).
+///
+/// # Example
+/// ```
+/// // Buttons adding to counters: (3) (1,3) (2) (2,3) (0,2) (0,1) reaching {3,5,4,7}
+/// let a = vec![
+///     vec![0, 0, 0, 0, 1, 1],
+///     vec![0, 1, 0, 0, 0, 1],
+///     vec![0, 0, 1, 1, 1, 0],
+///     vec![1, 1, 0, 1, 0, 0],
+/// ];
+/// let presses = aoc_utils::minimize_sum_ilp(&a, &[3, 5, 4, 7]).unwrap();
+/// assert_eq!(presses.iter().sum::<i64>(), 10);
+/// ```
+pub fn minimize_sum_ilp(a: &[Vec<i64>], b: &[i64]) -> Option<Vec<i64>> {
+    let n_vars = a
+        .iter()
+        .map(|row| row.len())
+        .max()
+        .unwrap_or(0);
+    if a.iter().flatten().any(|&v| v < 0) {
+        return None;
+    }
+    let upper_bounds: Vec<i64> = (0..n_vars)
+        .map(|j| {
+            a.iter()
+                .zip(b)
+                .filter_map(|(row, &rhs)| {
+                    let coef = row.get(j).copied().unwrap_or(0);
+                    (coef > 0).then(|| rhs / coef)
+                })
+                .min()
+                .unwrap_or(0)
+        })
+        .collect();
+    LinearSystem::new(a, b).min_sum_nonnegative(&upper_bounds)
+}
+
+/// Solution set of an XOR system over GF(2), variables being bits of a `u64`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gf2System {
+    particular: u64,
+    nullspace: Vec<u64>,
+}
+
+impl Gf2System {
+    /// Solves equations given as `(mask of variables, expected parity)`, or returns
+    /// `None` when they contradict each other.
+    pub fn solve(equations: &[(u64, bool)], n_vars: usize) -> Option<Self> {
+        assert!(n_vars <= 64, "GF(2) systems are limited to 64 variables");
+        let mut rows: Vec<(u64, bool)> = equations.to_vec();
+        let mut pivots = Vec::new();
+        let mut pivot_row = 0;
+        for var in 0..n_vars {
+            let bit = 1u64 << var;
+            let Some(found) = (pivot_row..rows.len()).find(|&r| rows[r].0 & bit != 0) else {
+                continue;
+            };
+            rows.swap(pivot_row, found);
+            let (mask, parity) = rows[pivot_row];
+            for (r, row) in rows.iter_mut().enumerate() {
+                if r != pivot_row && row.0 & bit != 0 {
+                    row.0 ^= mask;
+                    row.1 ^= parity;
+                }
+            }
+            pivots.push(var);
+            pivot_row += 1;
+        }
+        if rows[pivot_row..].iter().any(|&(_, parity)| parity) {
+            return None;
+        }
+
+        let pivot_mask = pivots.iter().fold(0u64, |acc, &var| acc | (1 << var));
+        let particular = rows[..pivot_row]
+            .iter()
+            .zip(&pivots)
+            .filter(|((_, parity), _)| *parity)
+            .fold(0u64, |acc, (_, &var)| acc | (1 << var));
+        // Setting one free variable forces every pivot row that contains it
+        let nullspace = (0..n_vars)
+            .filter(|var| pivot_mask & (1 << var) == 0)
+            .map(|free| {
+                rows[..pivot_row]
+                    .iter()
+                    .zip(&pivots)
+                    .filter(|((mask, _), _)| mask & (1 << free) != 0)
+                    .fold(1u64 << free, |acc, (_, &var)| acc | (1 << var))
+            })
+            .collect();
+        Some(Gf2System { particular, nullspace })
+    }
+
+    pub fn particular(&self) -> u64 {
+        self.particular
+    }
+
+    pub fn nullspace(&self) -> &[u64] {
+        &self.nullspace
+    }
+
+    /// Returns an iterator over all `2^k` solutions, `k` being the number of free variables,
+    /// which can be up to 64
+    pub fn solutions(&self) -> impl Iterator<Item = u64> + '_ {
+        (0..1u128 << self.nullspace.len()).map(move |combination| {
+            self.nullspace
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| combination & (1 << i) != 0)
+                .fold(self.particular, |acc, (_, basis)| acc ^ basis)
+        })
+    }
+
+    /// Solution with the fewest variables set, found by trying all `2^k` solutions, so
+    /// only practical for a few dozen free variables at most
+    pub fn min_weight_solution(&self) -> u64 {
+        self.solutions()
+            .min_by_key(|solution| solution.count_ones())
+            .unwrap_or(self.particular)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lcg(seed: &mut u64) -> u64 {
+        *seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        *seed >> 33
+    }
+
+    #[test]
+    fn rationals_stay_reduced() {
+        let half = Rational::new(3, 6);
+        assert_eq!((half.numerator(), half.denominator()), (1, 2));
+        assert_eq!(Rational::new(2, -4), Rational::new(-1, 2));
+        assert_eq!(half + Rational::new(1, 3), Rational::new(5, 6));
+        assert_eq!(half - Rational::one(), Rational::new(-1, 2));
+        assert_eq!(half * Rational::from(4), Rational::from(2));
+        assert_eq!(Rational::from(3) / Rational::new(3, 4), Rational::from(4));
+        assert!(Rational::new(-1, 3) < Rational::zero() && Rational::new(2, 3) > half);
+        assert_eq!(Rational::new(6, 3).to_integer(), Some(2));
+        assert_eq!(half.to_integer(), None);
+        assert_eq!((half.to_string(), Rational::from(-7).to_string()), ("1/2".into(), "-7".into()));
+    }
+
+    #[test]
+    fn inconsistent_system_has_no_solution() {
+        let system = LinearSystem::new(&[vec![1, 1], vec![2, 2]], &[1, 3]);
+        assert!(!system.is_consistent());
+        assert_eq!(system.solve_with(&[Rational::zero()]), None);
+        assert_eq!(system.min_sum_nonnegative(&[10, 10]), None);
+        assert_eq!(minimize_sum_ilp(&[vec![1, 1], vec![2, 2]], &[1, 3]), None);
+    }
+
+    #[test]
+    fn unique_solution_is_exact() {
+        let system = LinearSystem::new(&[vec![2, 1], vec![1, -1]], &[5, 1]);
+        assert!(system.free_variables().is_empty());
+        assert_eq!(system.unique_solution(), Some(vec![Rational::from(2), Rational::from(1)]));
+        let halves = LinearSystem::new(&[vec![2]], &[1]);
+        assert_eq!(halves.unique_solution(), Some(vec![Rational::new(1, 2)]));
+        assert_eq!(halves.min_sum_nonnegative(&[5]), None);
+    }
+
+    #[test]
+    fn free_variables_respect_their_bounds() {
+        // x + 2y = 4: (4, 0), (2, 1) or (0, 2)
+        let system = LinearSystem::new(&[vec![1, 2]], &[4]);
+        assert_eq!(system.pivot_variables(), [0]);
+        assert_eq!(system.free_variables(), [1]);
+        assert_eq!(system.min_sum_nonnegative(&[4, 2]), Some(vec![0, 2]));
+        assert_eq!(system.min_sum_nonnegative(&[4, 1]), Some(vec![2, 1]));
+        assert_eq!(system.min_sum_nonnegative(&[4, 0]), Some(vec![4, 0]));
+        // The pivot is bounded too
+        assert_eq!(system.min_sum_nonnegative(&[1, 1]), None);
+    }
+
+    /// Smallest sum of `x` in `0..=max` per variable with `a x = b`, by trying them all
+    fn brute_force_min_sum(a: &[Vec<i64>], b: &[i64], n_vars: usize, max: i64) -> Option<i64> {
+        let mut x = vec![0; n_vars];
+        let mut best = None;
+        loop {
+            let solves = a
+                .iter()
+                .zip(b)
+                .all(|(row, &rhs)| row.iter().zip(&x).map(|(c, v)| c * v).sum::<i64>() == rhs);
+            if solves {
+                let sum = x.iter().sum();
+                best = Some(best.map_or(sum, |best: i64| best.min(sum)));
+            }
+            let Some(j) = x.iter().position(|&v| v < max) else {
+                return best;
+            };
+            x[j] += 1;
+            x[..j].fill(0);
+        }
+    }
+
+    #[test]
+    fn ilp_matches_brute_force() {
+        let mut seed = 11;
+        for _ in 0..200 {
+            let n_vars = 1 + (lcg(&mut seed) % 4) as usize;
+            let n_rows = 1 + (lcg(&mut seed) % 3) as usize;
+            let a: Vec<Vec<i64>> = (0..n_rows)
+                .map(|_| (0..n_vars).map(|_| (lcg(&mut seed) % 3) as i64).collect())
+                .collect();
+            let b: Vec<i64> = if lcg(&mut seed).is_multiple_of(4) {
+                // Usually unreachable targets
+                (0..n_rows).map(|_| (lcg(&mut seed) % 7) as i64).collect()
+            } else {
+                let x: Vec<i64> = (0..n_vars).map(|_| (lcg(&mut seed) % 3) as i64).collect();
+                a.iter().map(|row| row.iter().zip(&x).map(|(c, v)| c * v).sum()).collect()
+            };
+
+            let found = minimize_sum_ilp(&a, &b);
+            // No variable of a non-negative system exceeds the largest target
+            let max = b.iter().copied().max().unwrap_or(0);
+            let expected = brute_force_min_sum(&a, &b, n_vars, max);
+            assert_eq!(found.as_ref().map(|x| x.iter().sum::<i64>()), expected, "{a:?} {b:?}");
+            if let Some(x) = found {
+                assert!(x.iter().all(|&v| v >= 0));
+                for (row, &rhs) in a.iter().zip(&b) {
+                    assert_eq!(row.iter().zip(&x).map(|(c, v)| c * v).sum::<i64>(), rhs);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gf2_min_weight_matches_brute_force() {
+        let mut seed = 5;
+        for _ in 0..200 {
+            let n_vars = 1 + (lcg(&mut seed) % 8) as usize;
+            let equations: Vec<(u64, bool)> = (0..1 + lcg(&mut seed) % 5)
+                .map(|_| (lcg(&mut seed) % (1 << n_vars), lcg(&mut seed) % 2 == 1))
+                .collect();
+            let satisfies = |x: u64| {
+                equations.iter().all(|&(mask, parity)| ((mask & x).count_ones() % 2 == 1) == parity)
+            };
+            let brute = (0..1u64 << n_vars).filter(|&x| satisfies(x)).map(u64::count_ones).min();
+            let system = Gf2System::solve(&equations, n_vars);
+            assert_eq!(system.as_ref().map(|s| s.min_weight_solution().count_ones()), brute);
+            if let Some(system) = system {
+                let count = (0..1u64 << n_vars).filter(|&x| satisfies(x)).count();
+                assert_eq!(system.solutions().count(), count);
+                assert!(system.solutions().all(satisfies));
+            }
+        }
+    }
+
+    #[test]
+    fn gf2_handles_64_free_variables() {
+        let system = Gf2System::solve(&[], 64).unwrap();
+        assert_eq!(system.nullspace().len(), 64);
+        let first: Vec<u64> = system.solutions().take(3).collect();
+        assert_eq!(first, [0, 1, 2]);
+        assert_eq!(Gf2System::solve(&[(0b11, true), (0b11, false)], 2), None);
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use crate::{ Direction, Grid, Position };
+
+/// Unbounded grid backed by a hash map. Bounds grow automatically as cells are inserted,
+/// including into negative coordinates.
+///
+/// Bounds never shrink on `remove`; call `shrink_bounds` when a tight box is needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Position, T>,
+    bounds: Option<(Position, Position)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, pos: Position, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (pos, pos),
+            Some((min, max)) =>
+                (
+                    Position::new(min.x.min(pos.x), min.y.min(pos.y)),
+                    Position::new(max.x.max(pos.x), max.y.max(pos.y)),
+                ),
+        });
+        self.cells.insert(pos, value)
+    }
+
+    pub fn get(&self, pos: &Position) -> Option<&T> {
+        self.cells.get(pos)
+    }
+
+    pub fn get_mut(&mut self, pos: &Position) -> Option<&mut T> {
+        self.cells.get_mut(pos)
+    }
+
+    pub fn remove(&mut self, pos: &Position) -> Option<T> {
+        self.cells.remove(pos)
+    }
+
+    /// Removes every cell and resets the bounds, keeping the allocation
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bounds = None;
+    }
+
+    pub fn contains(&self, pos: &Position) -> bool {
+        self.cells.contains_key(pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Inclusive `(min, max)` corners of every position ever inserted, `None` when empty
+    pub fn bounds(&self) -> Option<(Position, Position)> {
+        self.bounds
+    }
+
+    /// Recomputes the bounds from the cells currently stored
+    pub fn shrink_bounds(&mut self) {
+        self.bounds = self.cells.keys().fold(None, |bounds, pos| {
+            Some(match bounds {
+                None => (*pos, *pos),
+                Some((min, max)) =>
+                    (
+                        Position::new(min.x.min(pos.x), min.y.min(pos.y)),
+                        Position::new(max.x.max(pos.x), max.y.max(pos.y)),
+                    ),
+            })
+        });
+    }
+
+    /// Width and height of the bounding box
+    pub fn size(&self) -> (usize, usize) {
+        match self.bounds {
+            None => (0, 0),
+            Some((min, max)) => ((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize),
+        }
+    }
+
+    /// Returns an iterator over the stored cells, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&Position, &T)> {
+        self.cells.iter()
+    }
+
+    /// Returns an iterator over every position of the bounding box in row-major order,
+    /// along with its value if one is stored.
+    pub fn iter_bounds(&self) -> impl Iterator<Item = (Position, Option<&T>)> {
+        let (min, max) = self.bounds.unwrap_or((Position::new(0, 0), Position::new(-1, -1)));
+        (min.y..=max.y).flat_map(move |y| {
+            (min.x..=max.x).map(move |x| {
+                let pos = Position::new(x, y);
+                (pos, self.cells.get(&pos))
+            })
+        })
+    }
+
+    /// Returns the stored neighbors of a position in all 8 directions
+    pub fn neighbors(&self, pos: &Position) -> impl Iterator<Item = (Position, &T)> {
+        let pos = *pos;
+        Direction::all().filter_map(move |dir| {
+            let adjacent = pos.get_adjacent(&dir);
+            self.cells.get(&adjacent).map(|value| (adjacent, value))
+        })
+    }
+
+    /// Builds a sparse grid from the cells of `grid` for which `keep` returns true.
+    pub fn from_grid_filtered(grid: Grid<T>, keep: impl Fn(&T) -> bool) -> Self {
+        let cols = grid.cols;
+        grid.data
+            .into_iter()
+            .enumerate()
+            .filter(|(_, value)| keep(value))
+            .map(|(i, value)| (Position::new((i % cols) as i32, (i / cols) as i32), value))
+            .collect()
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Converts the bounding box into a dense `Grid`, filling missing cells with `fill`.
+    /// Cell `(0, 0)` of the result is the `min` corner of `bounds()`.
+    pub fn to_grid(&self, fill: T) -> Grid<T> {
+        let (cols, rows) = self.size();
+        let mut grid = Grid::new(rows, cols, fill);
+        if let Some((min, _)) = self.bounds {
+            for (pos, value) in &self.cells {
+                grid.set(&Position::new(pos.x - min.x, pos.y - min.y), value.clone());
+            }
+        }
+        grid
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        SparseGrid::from_grid_filtered(grid, |_| true)
+    }
+}
+
+impl<T> FromIterator<(Position, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Position, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        for (pos, value) in iter {
+            grid.insert(pos, value);
+        }
+        grid
+    }
+}
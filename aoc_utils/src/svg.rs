@@ -0,0 +1,392 @@
+use std::fmt::{ self, Write };
+use std::fs;
+use std::io;
+use crate::{ Color, Graph, Position };
+
+const POINT_RADIUS: f64 = 4.0;
+const FONT_SIZE: f64 = 12.0;
+
+#[derive(Debug, Clone)]
+enum Shape {
+    Polyline {
+        points: Vec<Position>,
+        closed: bool,
+        color: Color,
+    },
+    Rect {
+        corner: Position,
+        opposite: Position,
+        color: Color,
+    },
+    Cell {
+        pos: Position,
+        color: Color,
+    },
+    Point {
+        pos: Position,
+        label: Option<String>,
+        color: Color,
+    },
+    Arrow {
+        from: Position,
+        to: Position,
+        color: Color,
+    },
+}
+
+impl Shape {
+    fn positions(&self) -> Vec<Position> {
+        match self {
+            Shape::Polyline { points, .. } => points.clone(),
+            Shape::Rect { corner, opposite, .. } => vec![*corner, *opposite],
+            Shape::Cell { pos, .. } => vec![*pos, Position::new(pos.x + 1, pos.y + 1)],
+            Shape::Point { pos, .. } => vec![*pos],
+            Shape::Arrow { from, to, .. } => vec![*from, *to],
+        }
+    }
+}
+
+/// How 3D points, such as junction boxes, are flattened onto the drawing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Projection {
+    /// Seen from above, keeping `(x, y)`
+    #[default]
+    Top,
+    /// Seen from the front, keeping `(x, z)` with `z` going up
+    Front,
+    /// Seen from the side, keeping `(y, z)` with `z` going up
+    Side,
+    /// 2:1 isometric view, `x` going down to the right, `y` down to the left and `z` up
+    Isometric,
+}
+
+impl Projection {
+    /// Position of `(x, y, z)` on the drawing
+    ///
+    /// # Panics
+    /// When the projected coordinates don't fit a `Position`
+    pub fn project(self, (x, y, z): (i64, i64, i64)) -> Position {
+        let (px, py) = match self {
+            Projection::Top => (x, y),
+            Projection::Front => (x, -z),
+            Projection::Side => (y, -z),
+            Projection::Isometric => (2 * (x - y), x + y - 2 * z),
+        };
+        let coordinate = |c: i64| i32::try_from(c).expect("Projected coordinate out of range");
+        Position::new(coordinate(px), coordinate(py))
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Maps puzzle coordinates to pixels, keeping the aspect ratio
+struct Transform {
+    min: Position,
+    scale: f64,
+    margin: f64,
+}
+
+impl Transform {
+    fn apply(&self, pos: Position) -> (f64, f64) {
+        (
+            self.margin + ((pos.x - self.min.x) as f64) * self.scale,
+            self.margin + ((pos.y - self.min.y) as f64) * self.scale,
+        )
+    }
+}
+
+/// Vector drawing of puzzle geometry, scaled so that everything fits in `size` pixels
+/// whatever the range of the coordinates. `y` grows downwards as in grids.
+///
+/// # Example
+/// ```
+/// use aoc_utils::{ Color, Position, Projection, Svg };
+/// let svg = Svg::new()
+///     .size(100)
+///     .margin(0)
+///     .polygon(
+///         [Position::new(0, 0), Position::new(10, 0), Position::new(10, 5)],
+///         Color::Ansi256(1)
+///     )
+///     .labeled_point(Position::new(10, 5), "corner", Color::Ansi256(2))
+///     .render_to_string();
+/// assert!(svg.contains("width=\"100\" height=\"50\""));
+/// assert!(svg.contains("<polygon points=\"0.0,0.0 100.0,0.0 100.0,50.0\""));
+/// assert!(svg.contains(">corner</text>"));
+///
+/// // 3D junction boxes seen from the front, `z` going up
+/// let boxes = [(162, 817, 812), (57, 618, 57), (906, 360, 560)];
+/// let svg = Svg::new()
+///     .size(100)
+///     .margin(0)
+///     .points_3d(boxes, Projection::Front, Color::Ansi256(3))
+///     .render_to_string();
+/// assert!(svg.contains("<circle cx=\"12.4\" cy=\"0.0\""));
+/// assert!(svg.contains("<circle cx=\"0.0\" cy=\"88.9\""));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Svg {
+    shapes: Vec<Shape>,
+    size: usize,
+    margin: usize,
+    background: Option<Color>,
+}
+
+impl Default for Svg {
+    fn default() -> Self {
+        Svg {
+            shapes: Vec::new(),
+            size: 800,
+            margin: 20,
+            background: None,
+        }
+    }
+}
+
+impl Svg {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pixel length of the longest side of the drawing, margins excluded, 800 by default
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Blank pixels around the drawing, 20 by default
+    pub fn margin(mut self, margin: usize) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    pub fn polyline(mut self, points: impl IntoIterator<Item = Position>, color: Color) -> Self {
+        let points = points.into_iter().collect();
+        self.shapes.push(Shape::Polyline { points, closed: false, color });
+        self
+    }
+
+    /// Closed outline, filled with a translucent shade of its color
+    pub fn polygon(mut self, points: impl IntoIterator<Item = Position>, color: Color) -> Self {
+        let points = points.into_iter().collect();
+        self.shapes.push(Shape::Polyline { points, closed: true, color });
+        self
+    }
+
+    /// Rectangle between two opposite corners
+    pub fn rect(mut self, corner: Position, opposite: Position, color: Color) -> Self {
+        self.shapes.push(Shape::Rect { corner, opposite, color });
+        self
+    }
+
+    /// Solid unit square covering `pos` to `pos + (1, 1)`, as a grid cell
+    pub fn cell(mut self, pos: Position, color: Color) -> Self {
+        self.shapes.push(Shape::Cell { pos, color });
+        self
+    }
+
+    pub fn point(mut self, pos: Position, color: Color) -> Self {
+        self.shapes.push(Shape::Point { pos, label: None, color });
+        self
+    }
+
+    pub fn labeled_point(mut self, pos: Position, label: impl Into<String>, color: Color) -> Self {
+        self.shapes.push(Shape::Point { pos, label: Some(label.into()), color });
+        self
+    }
+
+    pub fn points(self, points: impl IntoIterator<Item = Position>, color: Color) -> Self {
+        points.into_iter().fold(self, |svg, pos| svg.point(pos, color))
+    }
+
+    /// 3D points flattened by `projection`
+    pub fn points_3d(
+        self,
+        points: impl IntoIterator<Item = (i64, i64, i64)>,
+        projection: Projection,
+        color: Color
+    ) -> Self {
+        self.points(points.into_iter().map(|point| projection.project(point)), color)
+    }
+
+    /// Line ending with an arrow head, stopping short of a point drawn at `to`
+    pub fn arrow(mut self, from: Position, to: Position, color: Color) -> Self {
+        self.shapes.push(Shape::Arrow { from, to, color });
+        self
+    }
+
+    /// Draws the graph top to bottom following `Graph::layers`, nodes labeled with
+    /// their names
+    pub fn graph(self, graph: &Graph, node_color: Color, edge_color: Color) -> Self {
+        let mut positions = vec![Position::new(0, 0); graph.len()];
+        for (depth, layer) in graph.layers().iter().enumerate() {
+            // Layers centered on x = 0, two units between neighbors
+            let offset = (layer.len() as i32) - 1;
+            for (i, &id) in layer.iter().enumerate() {
+                positions[id] = Position::new(2 * (i as i32) - offset, 3 * (depth as i32));
+            }
+        }
+        let svg = graph
+            .edges()
+            .fold(self, |svg, (from, to)| svg.arrow(positions[from], positions[to], edge_color));
+        (0..graph.len()).fold(svg, |svg, id| {
+            svg.labeled_point(positions[id], graph.name(id), node_color)
+        })
+    }
+
+    fn transform(&self) -> (Transform, f64, f64) {
+        let positions: Vec<Position> = self.shapes.iter().flat_map(Shape::positions).collect();
+        let min = Position::new(
+            positions.iter().map(|p| p.x).min().unwrap_or(0),
+            positions.iter().map(|p| p.y).min().unwrap_or(0)
+        );
+        let max = Position::new(
+            positions.iter().map(|p| p.x).max().unwrap_or(0),
+            positions.iter().map(|p| p.y).max().unwrap_or(0)
+        );
+        let (span_x, span_y) = ((max.x - min.x) as f64, (max.y - min.y) as f64);
+        let scale = (self.size as f64) / span_x.max(span_y).max(1.0);
+        let margin = self.margin as f64;
+        let transform = Transform { min, scale, margin };
+        (transform, span_x * scale + 2.0 * margin, span_y * scale + 2.0 * margin)
+    }
+
+    pub fn render<W: Write>(&self, out: &mut W) -> fmt::Result {
+        let (transform, width, height) = self.transform();
+        writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" \
+             viewBox=\"0 0 {:.1} {:.1}\" font-family=\"monospace\" font-size=\"{}\">",
+            width,
+            height,
+            width,
+            height,
+            FONT_SIZE
+        )?;
+        if let Some(color) = self.background {
+            writeln!(out, "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>", color.hex())?;
+        }
+        if self.shapes.iter().any(|shape| matches!(shape, Shape::Arrow { .. })) {
+            writeln!(
+                out,
+                "<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+                 markerWidth=\"6\" markerHeight=\"6\" orient=\"auto-start-reverse\">\
+                 <path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"context-stroke\"/></marker></defs>"
+            )?;
+        }
+
+        for shape in &self.shapes {
+            match shape {
+                Shape::Polyline { points, closed, color } => {
+                    let coordinates: Vec<String> = points
+                        .iter()
+                        .map(|&pos| {
+                            let (x, y) = transform.apply(pos);
+                            format!("{:.1},{:.1}", x, y)
+                        })
+                        .collect();
+                    let (tag, fill) = if *closed {
+                        ("polygon", format!("fill=\"{}\" fill-opacity=\"0.25\"", color.hex()))
+                    } else {
+                        ("polyline", "fill=\"none\"".to_string())
+                    };
+                    writeln!(
+                        out,
+                        "<{} points=\"{}\" {} stroke=\"{}\" stroke-width=\"1.5\"/>",
+                        tag,
+                        coordinates.join(" "),
+                        fill,
+                        color.hex()
+                    )?;
+                }
+                Shape::Rect { corner, opposite, color } => {
+                    let (x1, y1) = transform.apply(*corner);
+                    let (x2, y2) = transform.apply(*opposite);
+                    writeln!(
+                        out,
+                        "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" \
+                         fill=\"{}\" fill-opacity=\"0.15\" stroke=\"{}\" stroke-width=\"1.5\"/>",
+                        x1.min(x2),
+                        y1.min(y2),
+                        (x2 - x1).abs(),
+                        (y2 - y1).abs(),
+                        color.hex(),
+                        color.hex()
+                    )?;
+                }
+                Shape::Cell { pos, color } => {
+                    let (x, y) = transform.apply(*pos);
+                    writeln!(
+                        out,
+                        "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" \
+                         fill=\"{}\" shape-rendering=\"crispEdges\"/>",
+                        x,
+                        y,
+                        transform.scale,
+                        transform.scale,
+                        color.hex()
+                    )?;
+                }
+                Shape::Point { pos, label, color } => {
+                    let (x, y) = transform.apply(*pos);
+                    writeln!(
+                        out,
+                        "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"{}\"/>",
+                        x,
+                        y,
+                        POINT_RADIUS,
+                        color.hex()
+                    )?;
+                    if let Some(label) = label {
+                        writeln!(
+                            out,
+                            "<text x=\"{:.1}\" y=\"{:.1}\">{}</text>",
+                            x + POINT_RADIUS + 2.0,
+                            y - POINT_RADIUS - 2.0,
+                            escape(label)
+                        )?;
+                    }
+                }
+                Shape::Arrow { from, to, color } => {
+                    let (x1, y1) = transform.apply(*from);
+                    let (x2, y2) = transform.apply(*to);
+                    let length = (x2 - x1).hypot(y2 - y1);
+                    // Stop at the edge of a node drawn at the target
+                    let shorten = if length > 0.0 {
+                        1.0 - (POINT_RADIUS + 1.0) / length
+                    } else {
+                        1.0
+                    };
+                    writeln!(
+                        out,
+                        "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{}\" \
+                         stroke-width=\"1.5\" marker-end=\"url(#arrow)\"/>",
+                        x1,
+                        y1,
+                        x1 + (x2 - x1) * shorten,
+                        y1 + (y2 - y1) * shorten,
+                        color.hex()
+                    )?;
+                }
+            }
+        }
+        writeln!(out, "</svg>")
+    }
+
+    pub fn render_to_string(&self) -> String {
+        let mut out = String::new();
+        self.render(&mut out).expect("Writing to a String can't fail");
+        out
+    }
+
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        fs::write(path, self.render_to_string())
+    }
+}
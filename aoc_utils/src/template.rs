@@ -0,0 +1,246 @@
+use std::env;
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+/// Template used when `new-day` gets no `--template`
+pub const DEFAULT_TEMPLATE: &str = "day_template";
+/// Directory searched before the user config and the workspace templates
+pub const TEMPLATE_DIR_VAR: &str = "AOC_TEMPLATES";
+
+/// Sample input from the puzzle text with the answers it should give
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Example {
+    pub fn new(input: &str) -> Self {
+        Example { input: input.trim_matches('\n').to_string(), part1: None, part2: None }
+    }
+
+    pub fn part1(mut self, answer: impl ToString) -> Self {
+        self.part1 = Some(answer.to_string());
+        self
+    }
+
+    pub fn part2(mut self, answer: impl ToString) -> Self {
+        self.part2 = Some(answer.to_string());
+        self
+    }
+}
+
+/// What is known about a day when its files are generated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    pub day: u32,
+    pub title: Option<String>,
+    pub examples: Vec<Example>,
+}
+
+impl Puzzle {
+    pub fn new(day: u32) -> Self {
+        Puzzle { day, title: None, examples: Vec::new() }
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn example(mut self, example: Example) -> Self {
+        self.examples.push(example);
+        self
+    }
+
+    pub fn url(&self, year: &str) -> String {
+        format!("https://adventofcode.com/{}/day/{}", year, self.day)
+    }
+}
+
+/// Raw string literal holding `text` on its own lines, with one more `#` than the
+/// longest `"#…` run in the text so that it can't end the literal early
+fn raw_literal(text: &str) -> String {
+    let longest = text
+        .match_indices('"')
+        .map(|(i, _)| text[i + 1..].chars().take_while(|&c| c == '#').count())
+        .max()
+        .unwrap_or(0);
+    let hashes = "#".repeat(longest + 1);
+    format!("r{}\"\n{}\n\"{}", hashes, text, hashes)
+}
+
+/// Name of the placeholder `{{name}}` starting `text`, `None` when the braces hold
+/// anything but an identifier, such as the escaped braces of a Rust format string
+fn placeholder(text: &str) -> Option<&str> {
+    let end = text.find("}}")?;
+    let name = text[2..end].trim();
+    let is_identifier =
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    is_identifier.then_some(name)
+}
+
+/// Replaces each `{{name}}` with its value, failing on unknown names. Other text
+/// between double braces, like `format!("{{}}")`, is kept as it is.
+fn substitute(text: &str, value: &dyn Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(name) = placeholder(rest) else {
+            out.push_str("{{");
+            rest = &rest[2..];
+            continue;
+        };
+        out.push_str(&value(name).ok_or(format!("Unknown placeholder `{{{{{}}}}}`", name))?);
+        rest = &rest[rest.find("}}").unwrap() + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Source of a day solution with placeholders:
+/// - `{{year}}`, `{{day}}` (two digits), `{{day_number}}`, `{{title}}` and `{{url}}`
+/// - `{{#examples}}` … `{{/examples}}`, repeated for each example with `{{index}}`,
+///   `{{input}}`, `{{input_literal}}` (the input as a raw string literal), `{{part1}}`
+///   and `{{part2}}`, answers defaulting to `0`, or once with a blank input when there
+///   are no examples. Answers go in as integer literals, so rendering fails on any
+///   other answer.
+///
+/// Section tags go on their own line, which is left out of the output.
+///
+/// # Example
+/// ```
+/// use aoc_utils::{ DayTemplate, Example, Puzzle };
+/// let template = DayTemplate::new(
+///     "// {{title}}: {{url}}\n\
+///      {{#examples}}\nassert_eq!(part1(\"{{input}}\"), {{part1}});\n{{/examples}}\n"
+/// );
+/// let puzzle = Puzzle::new(4)
+///     .title("Printing Department")
+///     .example(Example::new("@@.").part1(2))
+///     .example(Example::new(".@."));
+/// assert_eq!(
+///     template.render("2025", &puzzle).unwrap(),
+///     "// Printing Department: https://adventofcode.com/2025/day/4\n\
+///      assert_eq!(part1(\"@@.\"), 2);\nassert_eq!(part1(\".@.\"), 0);\n"
+/// );
+/// assert!(DayTemplate::new("{{answer}}").render("2025", &puzzle).is_err());
+/// let listed = Puzzle::new(17).example(Example::new("A: 729").part1("4,6,3"));
+/// assert!(template.render("2024", &listed).is_err());
+/// let rust = DayTemplate::new("println!(\"{{}} {{:?}}\", {{day}}, x);");
+/// assert_eq!(rust.render("2025", &puzzle).unwrap(), "println!(\"{{}} {{:?}}\", 04, x);");
+///
+/// let quoted = Puzzle::new(1).example(Example::new("say \"#hi\"#"));
+/// assert_eq!(
+///     DayTemplate::new("{{#examples}}\n{{input_literal}}\n{{/examples}}").render("2025", &quoted),
+///     Ok("r##\"\nsay \"#hi\"#\n\"##\n".to_string())
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayTemplate {
+    text: String,
+}
+
+impl DayTemplate {
+    pub fn new(text: &str) -> Self {
+        DayTemplate { text: text.to_string() }
+    }
+
+    /// Directories searched for templates, first match wins: `$AOC_TEMPLATES`, then
+    /// `aoc/templates` in the user config directory, then `templates` in the workspace
+    pub fn search_path(root: &Path) -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        if let Some(dir) = env::var_os(TEMPLATE_DIR_VAR) {
+            dirs.push(PathBuf::from(dir));
+        }
+        let config = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")));
+        if let Some(config) = config {
+            dirs.push(config.join("aoc/templates"));
+        }
+        dirs.push(root.join("templates"));
+        dirs
+    }
+
+    /// Loads `<name>.rs` from the first directory of the search path that has it
+    pub fn load(root: &Path, name: &str) -> Result<Self, String> {
+        let candidates: Vec<PathBuf> = DayTemplate::search_path(root)
+            .into_iter()
+            .map(|dir| dir.join(format!("{}.rs", name)))
+            .collect();
+        candidates
+            .iter()
+            .find_map(|path| fs::read_to_string(path).ok())
+            .map(|text| DayTemplate { text })
+            .ok_or_else(|| {
+                let searched: Vec<String> = candidates
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect();
+                format!("Template '{}' not found, searched {}", name, searched.join(", "))
+            })
+    }
+
+    pub fn render(&self, year: &str, puzzle: &Puzzle) -> Result<String, String> {
+        let value = |name: &str| {
+            match name {
+                "year" => Some(year.to_string()),
+                "day" => Some(format!("{:02}", puzzle.day)),
+                "day_number" => Some(puzzle.day.to_string()),
+                "title" => Some(puzzle.title.clone().unwrap_or(format!("Day {}", puzzle.day))),
+                "url" => Some(puzzle.url(year)),
+                _ => None,
+            }
+        };
+
+        // A blank example keeps the tests in place until the real ones are filled in
+        let blank = [Example::new("")];
+        let examples = if puzzle.examples.is_empty() { &blank[..] } else { &puzzle.examples };
+
+        let answers = examples.iter().flat_map(|example| [&example.part1, &example.part2]);
+        if let Some(answer) = answers.flatten().find(|a| a.trim().parse::<i128>().is_err()) {
+            return Err(format!("Answer '{}' is not an integer", answer));
+        }
+
+        let mut out = String::new();
+        let mut rest = self.text.as_str();
+        while let Some(start) = rest.find("{{#") {
+            let name_end = rest[start..].find("}}").ok_or("Unclosed `{{#` in template")? + start;
+            let name = rest[start + 3..name_end].trim();
+            if name != "examples" {
+                return Err(format!("Unknown section `{}`", name));
+            }
+            let closing = "{{/examples}}";
+            let body_start = name_end + 2;
+            let body_end = rest[body_start..]
+                .find(closing)
+                .ok_or("Missing `{{/examples}}` in template")? + body_start;
+            let body = rest[body_start..body_end].strip_prefix('\n').unwrap_or(
+                &rest[body_start..body_end]
+            );
+
+            out.push_str(&substitute(&rest[..start], &value)?);
+            for (i, example) in examples.iter().enumerate() {
+                let example_value = |name: &str| {
+                    match name {
+                        "index" => Some((i + 1).to_string()),
+                        "input" => Some(example.input.clone()),
+                        "input_literal" => Some(raw_literal(&example.input)),
+                        "part1" => Some(example.part1.clone().unwrap_or("0".to_string())),
+                        "part2" => Some(example.part2.clone().unwrap_or("0".to_string())),
+                        _ => value(name),
+                    }
+                };
+                out.push_str(&substitute(body, &example_value)?);
+            }
+            let after = &rest[body_end + closing.len()..];
+            rest = after.strip_prefix('\n').unwrap_or(after);
+        }
+        out.push_str(&substitute(rest, &value)?);
+        Ok(out)
+    }
+}
@@ -0,0 +1,180 @@
+use std::io::{ self, IsTerminal, Read };
+use std::process::{ Command, Stdio };
+use std::sync::mpsc::{ self, Receiver };
+
+/// Key pressed while the terminal is in raw mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    Backspace,
+    Escape,
+    /// Ctrl-C, which raw mode delivers as a key instead of a signal
+    Interrupt,
+}
+
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty").args(args).stdin(Stdio::inherit()).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Switches the terminal to unbuffered, unechoed input through `stty`, restoring the
+/// previous settings and showing the cursor again when dropped. Signal keys are turned
+/// off so that Ctrl-C arrives as `Key::Interrupt` and the terminal always gets
+/// restored, while output processing is kept so that `\n` still starts a new line.
+pub struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    /// Fails when stdin is not a terminal or `stty` is unavailable
+    pub fn enable() -> io::Result<Self> {
+        if !io::stdin().is_terminal() {
+            return Err(io::Error::other("stdin is not a terminal"));
+        }
+        let saved = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "-isig", "-ixon", "min", "1", "time", "0"])?;
+        print!("\x1B[?25l");
+        Ok(RawMode { saved })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        print!("\x1B[?25h");
+        let _ = io::Write::flush(&mut io::stdout());
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+/// Length of the UTF-8 sequence starting with `byte`, 1 for a byte that can't start one
+fn utf8_len(byte: u8) -> usize {
+    match byte {
+        0xc2..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf4 => 4,
+        _ => 1,
+    }
+}
+
+/// Decodes the keys of raw input, returning them with the number of bytes used. Only a
+/// UTF-8 sequence cut at the end is left over, to be completed by the next read. The
+/// terminal sends an arrow key as `ESC [ A` to `ESC [ D` in a single write, so an
+/// `ESC` without its tail in the same read is the Escape key itself.
+fn decode_keys(bytes: &[u8]) -> (Vec<Key>, usize) {
+    let mut keys = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let key = match bytes[i] {
+            0x1b => {
+                let arrow = match bytes.get(i + 1..i + 3) {
+                    Some([b'[' | b'O', b'A']) => Some(Key::Up),
+                    Some([b'[' | b'O', b'B']) => Some(Key::Down),
+                    Some([b'[' | b'O', b'C']) => Some(Key::Right),
+                    Some([b'[' | b'O', b'D']) => Some(Key::Left),
+                    _ => None,
+                };
+                if arrow.is_some() {
+                    i += 2;
+                }
+                arrow.unwrap_or(Key::Escape)
+            }
+            0x03 => Key::Interrupt,
+            b'\n' | b'\r' => Key::Enter,
+            0x7f | 0x08 => Key::Backspace,
+            byte if byte.is_ascii() => Key::Char(byte as char),
+            byte => {
+                let len = utf8_len(byte);
+                let sequence = &bytes[i..bytes.len().min(i + len)];
+                let continued = sequence[1..].iter().all(|&next| next & 0xc0 == 0x80);
+                if sequence.len() < len && continued {
+                    break;
+                }
+                match std::str::from_utf8(sequence) {
+                    Ok(text) => {
+                        i += sequence.len() - 1;
+                        Key::Char(text.chars().next().unwrap())
+                    }
+                    // Skip a single byte so that what follows is still decoded
+                    Err(_) => Key::Char(char::REPLACEMENT_CHARACTER),
+                }
+            }
+        };
+        keys.push(key);
+        i += 1;
+    }
+    (keys, i)
+}
+
+/// Reads stdin on a background thread and sends every decoded key, so that callers can
+/// wait for input with `recv_timeout` while animating.
+pub fn spawn_key_reader() -> Receiver<Key> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut stdin = io::stdin().lock();
+        let mut buffer = [0; 64];
+        let mut pending = Vec::new();
+        while let Ok(read) = stdin.read(&mut buffer) {
+            if read == 0 {
+                break;
+            }
+            pending.extend_from_slice(&buffer[..read]);
+            let (keys, used) = decode_keys(&pending);
+            pending.drain(..used);
+            for key in keys {
+                if sender.send(key).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+    receiver
+}
+
+/// Terminal size as (columns, rows), falling back to 80x24
+pub fn terminal_size() -> (usize, usize) {
+    stty(&["size"])
+        .ok()
+        .and_then(|size| {
+            let (rows, cols) = size.split_once(' ')?;
+            Some((cols.parse().ok()?, rows.parse().ok()?))
+        })
+        .unwrap_or((80, 24))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_arrows_escape_and_interrupt() {
+        let (keys, used) = decode_keys(b"\x1b[Aq\x1b\x03\r");
+        assert_eq!(keys, [Key::Up, Key::Char('q'), Key::Escape, Key::Interrupt, Key::Enter]);
+        assert_eq!(used, 7);
+    }
+
+    #[test]
+    fn decodes_utf8_across_reads() {
+        let bytes = "é→😀".as_bytes();
+        let (keys, used) = decode_keys(bytes);
+        assert_eq!(keys, [Key::Char('é'), Key::Char('→'), Key::Char('😀')]);
+        assert_eq!(used, bytes.len());
+
+        // A sequence cut by the end of a read waits for the rest
+        let (keys, used) = decode_keys(&bytes[..4]);
+        assert_eq!((keys, used), (vec![Key::Char('é')], 2));
+
+        let (keys, used) = decode_keys(b"\xe2x\xff");
+        let replacement = Key::Char(char::REPLACEMENT_CHARACTER);
+        assert_eq!(keys, [replacement, Key::Char('x'), replacement]);
+        assert_eq!(used, 3);
+        assert_eq!(decode_keys(b"\xe2x").0, [replacement, Key::Char('x')]);
+    }
+}
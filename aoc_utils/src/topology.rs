@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use crate::{ Direction, Grid, Position };
+
+/// Explicit exits: stepping from `(position, direction)` lands on the mapped position,
+/// now facing the mapped direction.
+pub type EdgeMap = HashMap<(Position, Direction), (Position, Direction)>;
+
+/// How a `Grid` behaves past its edges.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Topology {
+    /// Everything outside `rows`×`cols` is invalid
+    #[default]
+    Bounded,
+    /// Leaving one side re-enters from the opposite one, on the selected axes
+    Wrapping {
+        x: bool,
+        y: bool,
+    },
+    /// Steps listed in the map are teleported, every other step must stay in bounds
+    Stitched(EdgeMap),
+}
+
+impl Topology {
+    /// Wraps around on both axes
+    pub fn torus() -> Self {
+        Topology::Wrapping { x: true, y: true }
+    }
+
+    /// Folds a cube net into a stitched topology. The grid is cut into `face_size`
+    /// squares and the 6 squares whose top-left cell satisfies `is_face` are the faces.
+    ///
+    /// # Example
+    /// ```
+    /// use aoc_utils::{ Direction, Grid, Position, Topology };
+    /// // Layouts of the 2022 day 22 example and of the real inputs
+    /// let example = "  #\n###\n  ##";
+    /// let real = " ##\n #\n##\n#";
+    /// let net = |layout: &str, size: usize| -> Grid {
+    ///     let rows: Vec<String> = layout
+    ///         .lines()
+    ///         .flat_map(|line| {
+    ///             let row: String = line
+    ///                 .chars()
+    ///                 .flat_map(|c| std::iter::repeat(c).take(size))
+    ///                 .collect();
+    ///             std::iter::repeat(row.replace('#', ".")).take(size)
+    ///         })
+    ///         .collect();
+    ///     let grid: Grid = rows.join("\n").parse().unwrap();
+    ///     let topology = Topology::cube_net(&grid, size, |&c| c != ' ').unwrap();
+    ///     grid.with_topology(topology)
+    /// };
+    ///
+    /// // Leaving the right side of the middle band at row 5 enters the bottom right face
+    /// // from the top, as in the puzzle description
+    /// let grid = net(example, 4);
+    /// assert_eq!(
+    ///     grid.step(&Position::new(11, 5), &Direction::East),
+    ///     Some((Position::new(14, 8), Direction::South))
+    /// );
+    ///
+    /// // Going straight around the cube comes back to the start with the same facing
+    /// for grid in [net(example, 4), net(real, 5)] {
+    ///     let size = if grid.cols == 16 { 4 } else { 5 };
+    ///     for (start, _) in grid.iter().filter(|&(_, &c)| c == '.') {
+    ///         for dir in Direction::cardinals() {
+    ///             let (mut pos, mut facing) = (start, dir);
+    ///             for _ in 0..4 * size {
+    ///                 (pos, facing) = grid.step(&pos, &facing).unwrap();
+    ///                 assert_eq!(grid.get(&pos), Some(&'.'));
+    ///             }
+    ///             assert_eq!((pos, facing), (start, dir));
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn cube_net<T>(
+        grid: &Grid<T>,
+        face_size: usize,
+        is_face: impl Fn(&T) -> bool
+    ) -> Result<Self, String> {
+        fold_cube_net(grid, face_size, is_face).map(Topology::Stitched)
+    }
+
+    /// Moves one step from `pos` on a `rows`×`cols` grid, returning the landing
+    /// position and the new facing.
+    pub fn step(
+        &self,
+        pos: &Position,
+        dir: &Direction,
+        rows: usize,
+        cols: usize
+    ) -> Option<(Position, Direction)> {
+        let in_bounds = |p: &Position| {
+            p.x >= 0 && p.y >= 0 && (p.x as usize) < cols && (p.y as usize) < rows
+        };
+        match self {
+            Topology::Bounded => {
+                let next = pos.get_adjacent(dir);
+                in_bounds(&next).then_some((next, *dir))
+            }
+            Topology::Wrapping { x, y } => {
+                let mut next = pos.get_adjacent(dir);
+                if *x && cols > 0 {
+                    next.x = next.x.rem_euclid(cols as i32);
+                }
+                if *y && rows > 0 {
+                    next.y = next.y.rem_euclid(rows as i32);
+                }
+                in_bounds(&next).then_some((next, *dir))
+            }
+            Topology::Stitched(edges) => {
+                if let Some(&(next, new_dir)) = edges.get(&(*pos, *dir)) {
+                    return Some((next, new_dir));
+                }
+                let next = pos.get_adjacent(dir);
+                in_bounds(&next).then_some((next, *dir))
+            }
+        }
+    }
+}
+
+type Vec3 = [i32; 3];
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn neg(a: Vec3) -> Vec3 {
+    [-a[0], -a[1], -a[2]]
+}
+
+/// Placement of a face on the unit cube: local `(a, b)` in `[0, 1]²` maps to
+/// `origin + a * right + b * down`, and `inward` points into the cube.
+#[derive(Clone, Copy)]
+struct FacePlacement {
+    origin: Vec3,
+    right: Vec3,
+    down: Vec3,
+    inward: Vec3,
+}
+
+impl FacePlacement {
+    fn corner(&self, a: i32, b: i32) -> Vec3 {
+        [0, 1, 2].map(|i| self.origin[i] + a * self.right[i] + b * self.down[i])
+    }
+
+    /// Placement of the face glued on the given side, folded into the cube
+    fn fold(&self, side: Direction) -> FacePlacement {
+        let FacePlacement { origin, right, down, inward } = *self;
+        match side {
+            Direction::East => FacePlacement {
+                origin: add(origin, right),
+                right: inward,
+                down,
+                inward: neg(right),
+            },
+            Direction::West => FacePlacement {
+                origin: add(origin, inward),
+                right: neg(inward),
+                down,
+                inward: right,
+            },
+            Direction::South => FacePlacement {
+                origin: add(origin, down),
+                right,
+                down: inward,
+                inward: neg(down),
+            },
+            _ => FacePlacement {
+                origin: add(origin, inward),
+                right,
+                down: neg(inward),
+                inward: down,
+            },
+        }
+    }
+
+    /// Cube corners at both ends of a side, ordered like the cells along that side
+    fn edge(&self, side: Direction) -> (Vec3, Vec3) {
+        match side {
+            Direction::North => (self.corner(0, 0), self.corner(1, 0)),
+            Direction::East => (self.corner(1, 0), self.corner(1, 1)),
+            Direction::South => (self.corner(0, 1), self.corner(1, 1)),
+            _ => (self.corner(0, 0), self.corner(0, 1)),
+        }
+    }
+}
+
+/// `i`-th cell along a side of the face whose top-left cell is `face`
+fn edge_cell(face: Position, size: i32, side: Direction, i: i32) -> Position {
+    match side {
+        Direction::North => Position::new(face.x + i, face.y),
+        Direction::East => Position::new(face.x + size - 1, face.y + i),
+        Direction::South => Position::new(face.x + i, face.y + size - 1),
+        _ => Position::new(face.x, face.y + i),
+    }
+}
+
+fn fold_cube_net<T>(
+    grid: &Grid<T>,
+    face_size: usize,
+    is_face: impl Fn(&T) -> bool
+) -> Result<EdgeMap, String> {
+    if
+        face_size == 0 ||
+        !grid.rows.is_multiple_of(face_size) ||
+        !grid.cols.is_multiple_of(face_size)
+    {
+        return Err(
+            format!("Grid of {}x{} can't be cut in faces of {}", grid.cols, grid.rows, face_size)
+        );
+    }
+    let size = face_size as i32;
+    let faces: Vec<Position> = (0..grid.rows / face_size)
+        .flat_map(|fy| (0..grid.cols / face_size).map(move |fx| (fx, fy)))
+        .map(|(fx, fy)| Position::new((fx * face_size) as i32, (fy * face_size) as i32))
+        .filter(|pos| grid.get(pos).is_some_and(&is_face))
+        .collect();
+    if faces.len() != 6 {
+        return Err(format!("A cube net needs 6 faces, found {}", faces.len()));
+    }
+
+    // Walk the net from the first face, folding each neighbor onto the cube
+    let mut placements: HashMap<Position, FacePlacement> = HashMap::new();
+    placements.insert(faces[0], FacePlacement {
+        origin: [0, 0, 0],
+        right: [1, 0, 0],
+        down: [0, 1, 0],
+        inward: [0, 0, 1],
+    });
+    let mut queue = vec![faces[0]];
+    while let Some(face) = queue.pop() {
+        let placement = placements[&face];
+        for side in Direction::cardinals() {
+            let (dx, dy) = side.delta();
+            let neighbor = Position::new(face.x + dx * size, face.y + dy * size);
+            if faces.contains(&neighbor) && !placements.contains_key(&neighbor) {
+                placements.insert(neighbor, placement.fold(side));
+                queue.push(neighbor);
+            }
+        }
+    }
+    if placements.len() != 6 {
+        return Err("Cube net faces are not connected".to_string());
+    }
+
+    let sides: Vec<(Position, Direction, (Vec3, Vec3))> = faces
+        .iter()
+        .flat_map(|face| Direction::cardinals().map(move |side| (*face, side)))
+        .map(|(face, side)| (face, side, placements[&face].edge(side)))
+        .collect();
+
+    let mut edges = EdgeMap::new();
+    for &(face, side, (start, end)) in &sides {
+        let (dx, dy) = side.delta();
+        if faces.contains(&Position::new(face.x + dx * size, face.y + dy * size)) {
+            continue;
+        }
+        let &(other, other_side, (other_start, _)) = sides
+            .iter()
+            .find(|&&(f, s, (a, b))| {
+                (f, s) != (face, side) && ((a, b) == (start, end) || (a, b) == (end, start))
+            })
+            .ok_or_else(|| "Cube net has an unmatched edge".to_string())?;
+        let reversed = other_start != start;
+        for i in 0..size {
+            let j = if reversed { size - 1 - i } else { i };
+            edges.insert(
+                (edge_cell(face, size, side, i), side),
+                (edge_cell(other, size, other_side, j), other_side.opposite())
+            );
+        }
+    }
+    Ok(edges)
+}
@@ -0,0 +1,213 @@
+use crate::{ Grid, Position, Topology };
+
+impl<T: Clone> Grid<T> {
+    /// Builds a `rows`×`cols` grid where cell `(x, y)` is copied from index `source(x, y)`.
+    fn remap(
+        &self,
+        rows: usize,
+        cols: usize,
+        swap_axes: bool,
+        source: impl Fn(usize, usize) -> usize
+    ) -> Grid<T> {
+        let data = (0..rows * cols)
+            .map(|i| self.data[source(i % cols, i / cols)].clone())
+            .collect();
+        // Wrapping survives any transform, stitched edges refer to positions that moved
+        let topology = match self.topology {
+            Topology::Wrapping { x, y } if swap_axes => Topology::Wrapping { x: y, y: x },
+            Topology::Wrapping { x, y } => Topology::Wrapping { x, y },
+            _ => Topology::Bounded,
+        };
+        Grid { data, rows, cols, topology }
+    }
+
+    /// Swaps rows and columns, so that column `x` becomes row `x`.
+    ///
+    /// # Example
+    /// ```
+    /// let grid: aoc_utils::Grid = "abc\ndef".parse().unwrap();
+    /// let transposed = grid.transpose();
+    /// assert_eq!(transposed.data.iter().collect::<String>(), "adbecf");
+    /// ```
+    pub fn transpose(&self) -> Grid<T> {
+        let cols = self.cols;
+        self.remap(self.cols, self.rows, true, |x, y| x * cols + y)
+    }
+
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        let (rows, cols) = (self.rows, self.cols);
+        self.remap(cols, rows, true, |x, y| (rows - 1 - x) * cols + y)
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Grid<T> {
+        let cols = self.cols;
+        self.remap(cols, self.rows, true, |x, y| x * cols + (cols - 1 - y))
+    }
+
+    pub fn rotate_180(&self) -> Grid<T> {
+        let len = self.data.len();
+        self.remap(self.rows, self.cols, false, |x, y| len - 1 - (y * self.cols + x))
+    }
+
+    /// Mirrors left to right
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        let cols = self.cols;
+        self.remap(self.rows, cols, false, |x, y| y * cols + (cols - 1 - x))
+    }
+
+    /// Mirrors top to bottom
+    pub fn flip_vertical(&self) -> Grid<T> {
+        let (rows, cols) = (self.rows, self.cols);
+        self.remap(rows, cols, false, |x, y| (rows - 1 - y) * cols + x)
+    }
+
+    /// Repeats the grid `times_y` times vertically and `times_x` times horizontally.
+    pub fn tile(&self, times_y: usize, times_x: usize) -> Grid<T> {
+        let (rows, cols) = (self.rows, self.cols);
+        self.remap(rows * times_y, cols * times_x, false, |x, y| (y % rows) * cols + (x % cols))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y >= self.rows {
+            return None;
+        }
+        Some(&self.data[y * self.cols..(y + 1) * self.cols])
+    }
+
+    /// Returns the cells of column `x` from top to bottom, empty when out of bounds
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        let rows = if x < self.cols { self.rows } else { 0 };
+        (0..rows).map(move |y| &self.data[y * self.cols + x])
+    }
+
+    /// Borrows the `rows`×`cols` rectangle whose top-left corner is `origin`,
+    /// or `None` if it doesn't fit in the grid.
+    pub fn view(&self, origin: Position, rows: usize, cols: usize) -> Option<GridView<'_, T>> {
+        let fits =
+            origin.x >= 0 &&
+            origin.y >= 0 &&
+            (origin.x as usize) + cols <= self.cols &&
+            (origin.y as usize) + rows <= self.rows;
+        fits.then_some(GridView { grid: self, origin, rows, cols })
+    }
+}
+
+/// Read-only rectangular window into a `Grid`, addressed from its own `(0, 0)`.
+#[derive(Debug, Clone, Copy)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    pub origin: Position,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn is_pos_valid(&self, pos: &Position) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.cols && (pos.y as usize) < self.rows
+    }
+
+    pub fn get(&self, pos: &Position) -> Option<&'a T> {
+        if !self.is_pos_valid(pos) {
+            return None;
+        }
+        self.grid.get(&Position::new(self.origin.x + pos.x, self.origin.y + pos.y))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&'a [T]> {
+        if y >= self.rows {
+            return None;
+        }
+        let start = (self.origin.y as usize + y) * self.grid.cols + (self.origin.x as usize);
+        Some(&self.grid.data[start..start + self.cols])
+    }
+
+    /// Returns an iterator over every cell in row-major order, with view-relative positions
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &'a T)> + '_ {
+        (0..self.rows).flat_map(move |y| {
+            (0..self.cols).filter_map(move |x| {
+                let pos = Position::new(x as i32, y as i32);
+                self.get(&pos).map(|value| (pos, value))
+            })
+        })
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid {
+            data: self
+                .iter()
+                .map(|(_, value)| value.clone())
+                .collect(),
+            rows: self.rows,
+            cols: self.cols,
+            topology: Topology::Bounded,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2 rows of 3 columns, so that swapped axes show up in the shape
+    fn grid() -> Grid {
+        "abc\ndef".parse().unwrap()
+    }
+
+    fn cells(grid: &Grid) -> (usize, usize, String) {
+        (grid.rows, grid.cols, grid.data.iter().collect())
+    }
+
+    #[test]
+    fn rotations() {
+        let grid = grid();
+        assert_eq!(cells(&grid.rotate_clockwise()), (3, 2, "daebfc".to_string()));
+        assert_eq!(cells(&grid.rotate_counter_clockwise()), (3, 2, "cfbead".to_string()));
+        assert_eq!(cells(&grid.rotate_180()), (2, 3, "fedcba".to_string()));
+        assert_eq!(grid.rotate_180(), grid.rotate_clockwise().rotate_clockwise());
+    }
+
+    #[test]
+    fn four_clockwise_rotations_are_the_identity() {
+        let grid = grid();
+        let turned = (0..4).fold(grid.clone(), |grid, _| grid.rotate_clockwise());
+        assert_eq!(turned, grid);
+    }
+
+    #[test]
+    fn counter_clockwise_undoes_clockwise() {
+        let grid = grid();
+        assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+        assert_eq!(grid.rotate_counter_clockwise().rotate_clockwise(), grid);
+    }
+
+    #[test]
+    fn flips_are_involutions() {
+        let grid = grid();
+        assert_eq!(cells(&grid.flip_horizontal()), (2, 3, "cbafed".to_string()));
+        assert_eq!(cells(&grid.flip_vertical()), (2, 3, "defabc".to_string()));
+        assert_eq!(grid.flip_horizontal().flip_horizontal(), grid);
+        assert_eq!(grid.flip_vertical().flip_vertical(), grid);
+    }
+
+    #[test]
+    fn tile_repeats_both_ways() {
+        let tiled = grid().tile(2, 2);
+        assert_eq!(cells(&tiled), (4, 6, "abcabcdefdefabcabcdefdef".to_string()));
+    }
+
+    #[test]
+    fn view_is_relative_to_its_origin() {
+        let grid = grid();
+        let view = grid.view(Position::new(1, 0), 2, 2).unwrap();
+        assert_eq!(view.get(&Position::new(0, 1)), Some(&'e'));
+        assert_eq!(view.get(&Position::new(2, 0)), None);
+        assert_eq!(view.row(1), Some(&['e', 'f'][..]));
+        assert_eq!(cells(&view.to_grid()), (2, 2, "bcef".to_string()));
+        assert!(grid.view(Position::new(2, 0), 2, 2).is_none());
+        assert!(grid.view(Position::new(-1, 0), 1, 1).is_none());
+    }
+}
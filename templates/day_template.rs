@@ -1,3 +1,5 @@
+//! Day {{day_number}}: {{title}}
+//! {{url}}
 use std::time::Duration;
 use progress_timer::time_function;
 
@@ -10,7 +12,7 @@ fn part2(input: &str) -> usize {
 }
 
 fn main() {
-    let is_test = true;
+    let is_test = false;
     let input = aoc_utils::get_input_for_day(is_test);
     let result_part_1 = time_function(
         "Part 1",
@@ -32,59 +34,20 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+{{#examples}}
 
     #[test]
-    fn test_part1_example1() {
-        let input = r#"
-test
-input
-here
-"#.trim();
+    fn test_part1_example{{index}}() {
+        let input = {{input_literal}}.trim();
         let result = part1(input);
-        assert_eq!(result, 0);
+        assert_eq!(result, {{part1}});
     }
 
     #[test]
-    fn test_part1_example2() {
-        let input = r#"
-another
-test
-case
-"#.trim();
-        let result = part1(input);
-        assert_eq!(result, 0);
-    }
-
-    #[test]
-    fn test_part2_example1() {
-        let input = r#"
-part2
-test
-input
-"#.trim();
-        let result = part2(input);
-        assert_eq!(result, 0);
-    }
-
-    #[test]
-    fn test_part2_example2() {
-        let input = r#"
-another
-part2
-case
-"#.trim();
-        let result = part2(input);
-        assert_eq!(result, 0);
-    }
-
-    #[test]
-    fn test_part2_edge_case() {
-        let input = r#"
-edge
-case
-test
-"#.trim();
+    fn test_part2_example{{index}}() {
+        let input = {{input_literal}}.trim();
         let result = part2(input);
-        assert_eq!(result, 0);
+        assert_eq!(result, {{part2}});
     }
+{{/examples}}
 }
@@ -0,0 +1,64 @@
+//! Day {{day_number}}: {{title}}
+//! {{url}}
+use std::time::Duration;
+use progress_timer::time_function;
+use aoc_utils::{ ints, sections };
+
+/// Numbers of each line, grouped by blank-line separated section
+fn parse(input: &str) -> Vec<Vec<Vec<i64>>> {
+    sections(input)
+        .iter()
+        .map(|section| section.parse_lines(ints).unwrap())
+        .collect()
+}
+
+fn part1(input: &str) -> usize {
+    let sections = parse(input);
+    0
+}
+
+fn part2(input: &str) -> usize {
+    let sections = parse(input);
+    0
+}
+
+fn main() {
+    let is_test = false;
+    let input = aoc_utils::get_input_for_day(is_test);
+    let result_part_1 = time_function(
+        "Part 1",
+        Duration::from_secs(5),
+        Duration::from_millis(100),
+        || part1(&input)
+    );
+    println!("Part 1: {}", result_part_1);
+
+    let result_part_2 = time_function(
+        "Part 2",
+        Duration::from_secs(5),
+        Duration::from_millis(100),
+        || part2(&input)
+    );
+    println!("Part 2: {}", result_part_2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+{{#examples}}
+
+    #[test]
+    fn test_part1_example{{index}}() {
+        let input = {{input_literal}}.trim();
+        let result = part1(input);
+        assert_eq!(result, {{part1}});
+    }
+
+    #[test]
+    fn test_part2_example{{index}}() {
+        let input = {{input_literal}}.trim();
+        let result = part2(input);
+        assert_eq!(result, {{part2}});
+    }
+{{/examples}}
+}
@@ -1,4 +1,5 @@
 use std::str::FromStr;
+use aoc_utils::{ ParseError, ints, scan, sections };
 
 struct PageOrder {
     before_page: usize,
@@ -6,16 +7,12 @@ struct PageOrder {
 }
 
 impl FromStr for PageOrder {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let nbs: Vec<usize> = s
-            .split('|')
-            .map(|str| str.parse().unwrap())
-            .collect();
-
-        let before_page = nbs[0];
-        let after_page = nbs[1];
+        let mut fields = scan("{}|{}", s)?;
+        let before_page = fields.parse_next()?;
+        let after_page = fields.parse_next()?;
 
         Ok(PageOrder { before_page, after_page })
     }
@@ -104,23 +101,13 @@ fn sum_middle_page(
 }
 
 fn retrieve_data(input: &str) -> (Vec<PageOrder>, Vec<UpdateSequence>) {
-    let parts: Vec<&str> = input.split("\n\n").collect();
+    let parts = sections(input);
 
-    let pages: Vec<PageOrder> = parts[0]
-        .lines()
-        .map(|line| line.parse().unwrap())
-        .collect();
+    let pages: Vec<PageOrder> = parts[0].parse_lines(str::parse).unwrap();
 
     let updates: Vec<UpdateSequence> = parts[1]
-        .lines()
-        .map(|line| {
-            let update_sequence: UpdateSequence = line
-                .split(',')
-                .map(|nb| nb.parse().unwrap())
-                .collect();
-            update_sequence
-        })
-        .collect();
+        .parse_lines(|line| ints(line).map(UpdateSequence::from_iter))
+        .unwrap();
 
     (pages, updates)
 }
@@ -1,9 +1,9 @@
-use std::collections::HashSet;
 use std::ops::Add;
 use std::time::Duration;
 use rayon::iter::ParallelIterator;
 use rayon::prelude::IntoParallelIterator;
 use progress_timer::time_function;
+use aoc_utils::find_cycle_or_end;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 enum Direction {
@@ -128,33 +128,36 @@ fn part1(input: &str) -> usize {
         .count()
 }
 
-fn is_loop_with_obstacle(input: &str, obstacle_pos: Coordinate) -> bool {
-    let mut map = MapData::new(input);
+fn next_guard_state(
+    map: &MapData,
+    obstacle_pos: Coordinate,
+    (pos, dir): (Coordinate, Direction)
+) -> Option<(Coordinate, Direction)> {
+    let rows = map.matrix.len() as isize;
+    let cols = map.matrix[0].len() as isize;
+    let next = pos + dir.movement();
+
+    if next.row < 0 || next.row >= rows || next.col < 0 || next.col >= cols {
+        return None;
+    }
+
+    let is_blocked =
+        next == obstacle_pos || map.matrix[next.row as usize][next.col as usize] == MapElement::OBSTACLE;
+    if is_blocked {
+        Some((pos, dir.rotate_right()))
+    } else {
+        Some((next, dir))
+    }
+}
 
+fn is_loop_with_obstacle(map: &MapData, obstacle_pos: Coordinate) -> bool {
     if obstacle_pos == map.pos {
         return false;
     }
 
-    map.matrix[obstacle_pos.row as usize][obstacle_pos.col as usize] = MapElement::OBSTACLE;
-
-    let mut visited = HashSet::new();
-    let mut current_dir = Direction::UP;
-
-    loop {
-        let current_state = (map.pos, current_dir);
-        if visited.contains(&current_state) {
-            return true;
-        }
-        visited.insert(current_state);
-
-        if can_move_outside(current_dir, &mut map) {
-            return false;
-        }
-
-        if !move_in_direction(current_dir, &mut map) {
-            current_dir = current_dir.rotate_right();
-        }
-    }
+    find_cycle_or_end((map.pos, Direction::UP), |&state| {
+        next_guard_state(map, obstacle_pos, state)
+    }).is_some()
 }
 
 fn part2(input: &str) -> usize {
@@ -175,7 +178,7 @@ fn part2(input: &str) -> usize {
 
     empty_positions
         .into_par_iter()
-        .filter(|&pos| is_loop_with_obstacle(input, pos))
+        .filter(|&pos| is_loop_with_obstacle(&map_data, pos))
         .count()
 }
 
@@ -1,6 +1,7 @@
 use std::{ str::FromStr, time::Duration };
 
 use progress_timer::time_function;
+use aoc_utils::{ ParseError, concat, key_values, parse_within };
 
 #[derive(Clone)]
 struct DataLine {
@@ -9,17 +10,11 @@ struct DataLine {
 }
 
 impl FromStr for DataLine {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split(':').collect();
-
-        let result = parts[0].parse().map_err(|_| "Failed to parse result as number".to_string())?;
-
-        let values = parts[1]
-            .split_ascii_whitespace()
-            .map(|nb| nb.parse().unwrap())
-            .collect();
+        let (result, values) = key_values(s)?;
+        let result = parse_within(s, result)?;
 
         Ok(DataLine { result, values })
     }
@@ -91,8 +86,7 @@ fn get_combination_with_concatenation(
             }
 
             2 => {
-                let str_result = result.to_string() + &next_nb.to_string();
-                result = str_result.parse().unwrap();
+                result = concat(result, next_nb);
             }
             _ => unreachable!(),
         }
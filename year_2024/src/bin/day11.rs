@@ -1,92 +1,29 @@
-use std::{ collections::HashMap, str::FromStr, time::Duration };
+use std::{ str::FromStr, time::Duration };
 use progress_timer::time_function;
-use rayon::iter::{ IntoParallelRefIterator, ParallelIterator };
+use aoc_utils::{ Counter, digit_count, split_at_digit };
 
 struct DataLine {
     stones: Vec<u64>,
 }
 
 impl DataLine {
-    fn replace(&mut self, index: u64, elem: u64) {
-        self.stones.remove(index as usize);
-        self.stones.insert(index as usize, elem);
-    }
-    fn nb_map_very_complex(&mut self, nb: u64, index: u64) -> bool {
-        match nb {
-            0 => {
-                self.replace(index, 1);
-                false
-            }
-            val if val.to_string().len() % 2 == 0 => {
-                let val_str = val.to_string();
-                let (left, right) = val_str.split_at(val_str.len() / 2);
-                let left_stone = left.parse::<u64>().expect("It should parse a number (u64)");
-                let right_stone = right.parse::<u64>().expect("It should parse a number (u64)");
-                self.replace(index, left_stone);
-                self.stones.insert((index as usize) + 1, right_stone);
-                true
-            }
-            _ => {
-                self.replace(index, nb * 2024);
-                false
-            }
-        }
-    }
-
     fn nb_map(&self, nb: u64) -> Vec<u64> {
         match nb {
             0 => { vec![1] }
-            val if val.to_string().len() % 2 == 0 => {
-                let val_str = val.to_string();
-                let (left, right) = val_str.split_at(val_str.len() / 2);
-
-                let left_stone = left.parse::<u64>().expect("It should parse a number (u64)");
-                let right_stone = right.parse::<u64>().expect("It should parse a number (u64)");
-
+            val if digit_count(val) % 2 == 0 => {
+                let (left_stone, right_stone) = split_at_digit(val, digit_count(val) / 2);
                 vec![left_stone, right_stone]
             }
             _ => { vec![nb * 2024] }
         }
     }
 
-    fn do_an_iter(&mut self, nb_of_iter: u32) {
-        for _iter in 0..nb_of_iter {
-            let mut i = 0;
-            while i < self.stones.len() {
-                let nb = self.stones[i];
-                if self.nb_map_very_complex(nb, i as u64) {
-                    i += 1;
-                }
-                i += 1;
-            }
-            // dbg!(&self.stones);
-            // println!("Doing iteration {}/{}", iter + 1, nb_of_iter);
+    fn count_after_blinks(&self, nb_of_iter: u32) -> usize {
+        let mut stones: Counter<u64> = self.stones.iter().copied().collect();
+        for _ in 0..nb_of_iter {
+            stones = stones.step(|&nb| self.nb_map(nb).into_iter().map(|n| (n, 1)));
         }
-    }
-
-    fn get_nb_of_elem(
-        &self,
-        nb: u64,
-        iter_nb: u32,
-        nb_of_iter: u32,
-        cache: &mut HashMap<(u64, u32), u64>
-    ) -> u64 {
-        if let Some(&result) = cache.get(&(nb, iter_nb)) {
-            return result;
-        }
-
-        if iter_nb == nb_of_iter {
-            return 1;
-        }
-
-        let sum = self
-            .nb_map(nb)
-            .into_iter()
-            .map(|n| self.get_nb_of_elem(n, iter_nb + 1, nb_of_iter, cache))
-            .sum();
-
-        cache.insert((nb, iter_nb), sum);
-        sum
+        stones.total()
     }
 }
 
@@ -104,22 +41,13 @@ impl FromStr for DataLine {
 }
 
 fn part1(input: &str) -> usize {
-    let mut data = DataLine::from_str(input).unwrap();
-    data.do_an_iter(25);
-    data.stones.len()
+    let data = DataLine::from_str(input).unwrap();
+    data.count_after_blinks(25)
 }
 
 fn part2(input: &str) -> usize {
     let data = DataLine::from_str(input).unwrap();
-    let nb_iter = 75;
-    let sum: u64 = data.stones
-        .par_iter()
-        .map(|&stone| {
-            let mut cache = HashMap::new();
-            data.get_nb_of_elem(stone, 0, nb_iter, &mut cache)
-        })
-        .sum();
-    sum as usize
+    data.count_after_blinks(75)
 }
 
 fn main() {
@@ -1,51 +1,30 @@
-use std::{ sync::OnceLock, time::Duration };
-use fancy_regex::Regex;
+use std::time::Duration;
 use progress_timer::time_function;
-use rayon::prelude::*;
-
-static RE_REPEAT_SEVERAL: OnceLock<Regex> = OnceLock::new();
-
-fn get_regex() -> &'static Regex {
-    RE_REPEAT_SEVERAL.get_or_init(|| Regex::new(r"^(.+?)\1+$").unwrap())
-}
-
-fn is_sequence_repeated(s: &str) -> bool {
-    let len = s.len();
-    if len % 2 != 0 {
-        return false;
-    }
-    let half = len / 2;
-    &s[0..half] == &s[half..len]
-}
-
-fn is_sequence_repeated_several(s: &str) -> bool {
-    get_regex().is_match(s).unwrap_or(false)
-}
-
-fn parse_range(range: &str) -> (usize, usize) {
-    let mut parts = range.split('-');
-    let start: usize = parts.next().unwrap().parse().unwrap();
-    let end: usize = parts.next().unwrap().parse().unwrap();
-    (start, end)
+use aoc_utils::{ range, repeated_numbers, repeated_numbers_any };
+
+fn parse_ranges(input: &str) -> Vec<(usize, usize)> {
+    input
+        .trim()
+        .split(',')
+        .map(|part| range(part).unwrap())
+        .collect()
 }
 
 fn part1(input: &str) -> usize {
-    let ranges: Vec<(usize, usize)> = input.trim().split(',').map(parse_range).collect();
+    let ranges = parse_ranges(input);
 
     ranges
-        .into_par_iter()
-        .flat_map(|(start, end)| start..=end)
-        .filter(|num| is_sequence_repeated(&num.to_string()))
+        .into_iter()
+        .flat_map(|(start, end)| repeated_numbers(start..=end, 2))
         .sum::<usize>()
 }
 
 fn part2(input: &str) -> usize {
-    let ranges: Vec<(usize, usize)> = input.trim().split(',').map(parse_range).collect();
+    let ranges = parse_ranges(input);
 
     ranges
-        .into_par_iter()
-        .flat_map(|(start, end)| start..=end)
-        .filter(|num| is_sequence_repeated_several(&num.to_string()))
+        .into_iter()
+        .flat_map(|(start, end)| repeated_numbers_any(start..=end))
         .sum::<usize>()
 }
 
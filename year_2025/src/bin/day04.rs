@@ -1,7 +1,7 @@
 use std::str::FromStr;
 use std::time::Duration;
 use progress_timer::time_function;
-use aoc_utils::Grid;
+use aoc_utils::{ Automaton, Grid };
 
 const ROLL_CHAR: char = '@';
 
@@ -26,37 +26,21 @@ fn part1(input: &str) -> usize {
 
 fn part2(input: &str) -> usize {
     let mut grid = Grid::from_str(input).unwrap();
-    let mut sum = 0;
-    loop {
-        let indices_to_remove: Vec<usize> = grid.data
+    let count_rolls = |grid: &Grid| {
+        grid.data
             .iter()
-            .enumerate()
-            .filter(|&(ref i, &c)| {
-                if c != ROLL_CHAR {
-                    return false;
-                }
-                let pos = grid.get_position_index(*i);
-                let neighbors = grid.check_all_directions(&pos);
-                neighbors
-                    .iter()
-                    .filter(|&c| c == &ROLL_CHAR)
-                    .count() < 4
-            })
-            .map(|(i, _)| i)
-            .collect();
-
-        for i in &indices_to_remove {
-            let pos = grid.get_position_index(*i);
-            grid.set(&pos, '.');
-        }
-
-        let removed_count = indices_to_remove.len();
-        if removed_count == 0 {
-            break;
-        }
-        sum += removed_count;
-    }
-    sum
+            .filter(|&&c| c == ROLL_CHAR)
+            .count()
+    };
+    let initial_rolls = count_rolls(&grid);
+    Automaton::new().run_grid(&mut grid, |_, &c, neighbors| {
+        let neighbor_rolls = neighbors
+            .iter()
+            .filter(|&&n| n == ROLL_CHAR)
+            .count();
+        if c == ROLL_CHAR && neighbor_rolls < 4 { '.' } else { c }
+    });
+    initial_rolls - count_rolls(&grid)
 }
 
 fn main() {
@@ -1,5 +1,6 @@
-use std::{ collections::HashMap, time::Duration };
+use std::time::Duration;
 use progress_timer::time_function;
+use aoc_utils::{ ColumnBlock, column_blocks };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Operator {
@@ -21,34 +22,7 @@ impl Operator {
     }
 }
 
-struct Problem {
-    numbersCache: std::collections::HashMap<usize, Vec<usize>>,
-    operatorCache: std::collections::HashMap<usize, Operator>,
-    sizeCache: std::collections::HashMap<usize, usize>,
-}
-
-impl Problem {
-    fn add_number(&mut self, id: usize, number: usize) {
-        self.numbersCache.entry(id).or_default().push(number);
-    }
-
-    fn set_operator(&mut self, id: usize, operator: Operator) {
-        self.operatorCache.insert(id, operator);
-    }
-
-    fn solve(&self) -> usize {
-        self.operatorCache
-            .iter()
-            .map(|(&id, &op)| {
-                let binding = Vec::new();
-                let numbers = self.numbersCache.get(&id).unwrap_or(&binding);
-                do_the_math(op, numbers)
-            })
-            .sum()
-    }
-}
-
-fn do_the_math(op: Operator, nbs: &Vec<usize>) -> usize {
+fn do_the_math(op: Operator, nbs: &[usize]) -> usize {
     match op {
         Operator::Add => nbs.iter().sum(),
         Operator::Subtract => nbs.iter().fold(0, |acc, &x| acc - x),
@@ -57,91 +31,28 @@ fn do_the_math(op: Operator, nbs: &Vec<usize>) -> usize {
     }
 }
 
-fn count_nb_whitespaces(s: &str) -> usize {
-    s.chars()
-        .filter(|c| c.is_whitespace())
-        .count()
+/// Sums every problem of the sheet, `read_numbers` turning the cells of a problem
+/// (without its operator line) into its numbers
+fn solve_sheet(input: &str, read_numbers: impl Fn(&ColumnBlock) -> Vec<String>) -> usize {
+    column_blocks(input)
+        .into_iter()
+        .filter_map(|mut block| {
+            let op = Operator::from_str(block.pop_row()?.trim())?;
+            let numbers: Vec<usize> = read_numbers(&block)
+                .iter()
+                .filter_map(|number| number.trim().parse().ok())
+                .collect();
+            Some(do_the_math(op, &numbers))
+        })
+        .sum()
 }
 
 fn part1(input: &str) -> usize {
-    let mut pb = Problem {
-        numbersCache: std::collections::HashMap::new(),
-        operatorCache: std::collections::HashMap::new(),
-        sizeCache: std::collections::HashMap::new(),
-    };
-    input.lines().for_each(|line| {
-        line.split_ascii_whitespace()
-            .enumerate()
-            .for_each(|(index, elem)| {
-                if let Some(op) = Operator::from_str(elem) {
-                    pb.set_operator(index, op);
-                } else if let Ok(num) = elem.parse::<usize>() {
-                    pb.add_number(index, num);
-                }
-            })
-    });
-
-    pb.solve()
+    solve_sheet(input, |block| block.rows.clone())
 }
 
 fn part2(input: &str) -> usize {
-    let mut pb = Problem {
-        numbersCache: std::collections::HashMap::new(),
-        operatorCache: std::collections::HashMap::new(),
-        sizeCache: std::collections::HashMap::new(),
-    };
-
-    let chars: Vec<char> = input.lines().last().unwrap().chars().collect();
-    let mut prev_index: Option<usize> = None;
-
-    chars
-        .iter()
-        .enumerate()
-        .for_each(|(index, &elem)| {
-            if let Some(op) = Operator::from_str(&elem.to_string()) {
-                if let Some(prev) = prev_index {
-                    let size = index - prev;
-                    pb.sizeCache.insert(prev, size);
-                }
-                pb.set_operator(index, op);
-                prev_index = Some(index);
-            }
-        });
-    if let Some(prev) = prev_index {
-        let size = chars.len() - prev;
-        pb.sizeCache.insert(prev, size);
-    }
-
-    // Remove last line which contains operators
-    let input_without_op = input
-        .rsplit_once('\n')
-        .map(|(rest, _last)| rest)
-        .unwrap_or(input)
-        .lines();
-
-    // Collect sizeCache to avoid borrowing conflict
-    let size_entries: Vec<(usize, usize)> = pb.sizeCache
-        .iter()
-        .map(|(&k, &v)| (k, v))
-        .collect();
-
-    // Iterate over each operator position to create numbers from columns
-    size_entries.iter().for_each(|&(op_index, size)| {
-        (op_index..op_index + size).for_each(|col_index| {
-            let vertical_number = input_without_op
-                .clone()
-                .filter_map(|line| { line.chars().nth(col_index) })
-
-                .collect::<String>()
-                .trim()
-                .to_string();
-            if let Ok(num) = vertical_number.parse::<usize>() {
-                pb.add_number(op_index, num);
-            }
-        });
-    });
-
-    pb.solve()
+    solve_sheet(input, ColumnBlock::columns_right_to_left)
 }
 
 fn main() {
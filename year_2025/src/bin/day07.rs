@@ -1,6 +1,6 @@
 use std::{ collections::{ HashMap, HashSet }, time::Duration };
 use progress_timer::time_function;
-use aoc_utils::{ Position, Direction, display_grid_animated };
+use aoc_utils::{ Color, Counter, Direction, Layer, Position, Renderer };
 
 fn part1(input: &str) -> usize {
     let splitter_hashset: std::collections::HashSet<Position> = input
@@ -46,14 +46,15 @@ fn part1(input: &str) -> usize {
     (0..height).for_each(|step| {
         let step = step as usize;
         let all_lasers: HashSet<Position> = laser_cache.values().flatten().cloned().collect();
-        display_grid_animated(
-            [
-                (&splitter_hashset, '^'),
-                (&all_lasers, '|'),
-            ],
-            step,
-            500
-        );
+        Renderer::new()
+            .layer(
+                Layer::new("splitter", '^', splitter_hashset.iter().copied())
+                    .z(1)
+                    .fg(Color::Ansi256(208))
+            )
+            .layer(Layer::new("beam", '|', all_lasers).fg(Color::Rgb(80, 200, 255)))
+            .legend(true)
+            .print_frame(&format!("Step {}", step), 500);
         let positions: Vec<Position> = laser_cache
             .get(&step)
             .unwrap_or(&HashSet::new())
@@ -103,80 +104,29 @@ fn part2(input: &str) -> usize {
         })
         .collect();
 
-    let mut laser_cache: HashMap<usize, HashMap<Position, usize>> = input
+    let mut beams: Counter<Position> = input
         .chars()
         .position(|c| c == 'S')
-        .map(|index| {
-            let mut map: HashMap<usize, HashMap<Position, usize>> = HashMap::new();
-            let mut inner_map: HashMap<Position, usize> = HashMap::new();
-            inner_map.insert(
-                Position {
-                    x: index as i32,
-                    y: 0,
-                },
-                1
-            );
-            map.insert(0, inner_map);
-            map
-        })
-        .unwrap_or_default();
-
-    let height = input.lines().count() as i32;
+        .map(|index| Position { x: index as i32, y: 0 })
+        .into_iter()
+        .collect();
 
-    (0..height).for_each(|step| {
-        let step = step as usize;
-        //display_grid_step(&splitter_hashset, &laser_cache, step);
-        let positions: Vec<Position> = laser_cache
-            .get(&step)
-            .unwrap_or(&HashMap::new())
-            .keys()
-            .cloned()
-            .collect();
+    let height = input.lines().count();
 
-        for pos in positions {
-            let current_paths = laser_cache.get(&step).unwrap().get(&pos).cloned().unwrap_or(0);
+    for _ in 0..height {
+        beams = beams.step(|pos| {
             if splitter_hashset.contains(&pos.get_adjacent(&Direction::South)) {
-                let south_east = pos.get_adjacent(&Direction::SouthEast);
-                let south_west = pos.get_adjacent(&Direction::SouthWest);
-
-                let se_nb = laser_cache
-                    .get(&(step + 1))
-                    .and_then(|m| m.get(&south_east))
-                    .cloned()
-                    .unwrap_or(0);
-                laser_cache
-                    .entry(step + 1)
-                    .or_default()
-                    .insert(south_east, se_nb + current_paths);
-
-                let sw_nb = laser_cache
-                    .get(&(step + 1))
-                    .and_then(|m| m.get(&south_west))
-                    .cloned()
-                    .unwrap_or(0);
-                laser_cache
-                    .entry(step + 1)
-                    .or_default()
-                    .insert(south_west, sw_nb + current_paths);
+                vec![
+                    (pos.get_adjacent(&Direction::SouthEast), 1),
+                    (pos.get_adjacent(&Direction::SouthWest), 1)
+                ]
             } else {
-                let south = pos.get_adjacent(&Direction::South);
-                let nb_s = laser_cache
-                    .get(&(step + 1))
-                    .and_then(|m| m.get(&south))
-                    .cloned()
-                    .unwrap_or(0);
-                laser_cache
-                    .entry(step + 1)
-                    .or_default()
-                    .insert(south, nb_s + current_paths);
+                vec![(pos.get_adjacent(&Direction::South), 1)]
             }
-        }
-    });
+        });
+    }
 
-    laser_cache
-        .get(&input.lines().count())
-        .map(|map| map.values().sum())
-        .unwrap_or(0)
+    beams.total()
 }
 
 fn main() {
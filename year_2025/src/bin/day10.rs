@@ -1,143 +1,105 @@
 use std::{ str::FromStr, time::Duration };
 use progress_timer::time_function;
-use good_lp::*;
+use aoc_utils::{ Gf2System, ParseError, bracketed, minimize_sum_ilp, scan };
 
 struct Machine {
     wanted_lights: usize,
     buttons: Vec<usize>,
     voltages: Vec<usize>,
-    ans: Vec<usize>,
 }
 
 impl Machine {
-    fn compute_min_presses_for_xor(&self, num: usize) -> Option<usize> {
-        let max_xor = (1 << self.ans.len()) - 1;
-        let mut dp = vec![usize::MAX; max_xor + 1];
-        dp[0] = 0;
-
-        for &button in &self.buttons {
-            let button_idx = button as usize;
-            for j in (0..max_xor).rev() {
-                if dp[j] != usize::MAX {
-                    let new_xor = j ^ button_idx;
-                    if new_xor <= max_xor {
-                        dp[new_xor] = dp[new_xor].min(dp[j] + 1);
-                    }
-                }
-            }
-        }
-
-        let target_idx = num as usize;
-        if dp[target_idx] == usize::MAX {
-            None
-        } else {
-            Some(dp[target_idx])
-        }
+    fn nb_lights(&self) -> usize {
+        self.voltages.len()
     }
 
-    // Need to install
-    fn min_presses_ilp(&self, target: &Vec<usize>) -> Option<usize> {
-        unsafe {
-            std::env::set_var("CBC_MESSAGE_LEVEL", "0");
-        }
-
-        let buttons = &self.buttons;
-        let n_bits = target.len();
-        let n_buttons = buttons.len();
-
-        let mut vars = ProblemVariables::new();
-        let x = vars.add_vector(variable().integer().min(0), n_buttons);
-
-        let mut objective = Expression::from(0);
-        for i in 0..n_buttons {
-            objective += x[i];
-        }
-
-        let mut problem = vars.minimise(objective).using(default_solver);
-
-        for bit in 0..n_bits {
-            let mut constraint_expr = Expression::from(0);
-
-            for (j, &button) in buttons.iter().enumerate() {
-                let bit_position = n_bits - 1 - bit;
-                if (button & (1 << bit_position)) != 0 {
-                    constraint_expr += x[j];
-                }
-            }
-
-            problem = problem.with(constraint!(constraint_expr == (target[bit] as i32)));
-        }
-
-        match problem.solve() {
-            Ok(solution) => {
-                let total: usize = (0..n_buttons).map(|i| solution.value(x[i]) as usize).sum();
-                Some(total)
-            }
-            Err(_) => None,
-        }
+    /// Whether `button` touches the light at `index`, counted from the left
+    fn toggles(&self, button: usize, index: usize) -> bool {
+        button & (1 << (self.nb_lights() - 1 - index)) != 0
     }
 
-    fn compute_xor_button_presses(&mut self) -> Option<usize> {
-        self.compute_min_presses_for_xor(self.wanted_lights)
+    fn compute_xor_button_presses(&self) -> Option<usize> {
+        let equations: Vec<(u64, bool)> = (0..self.nb_lights())
+            .map(|index| {
+                let mask = self.buttons
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &button)| self.toggles(button, index))
+                    .fold(0u64, |mask, (j, _)| mask | (1 << j));
+                (mask, self.toggles(self.wanted_lights, index))
+            })
+            .collect();
+        Gf2System::solve(&equations, self.buttons.len()).map(|system| {
+            system.min_weight_solution().count_ones() as usize
+        })
     }
 
-    fn compute_sum_button_presses(&mut self) -> Option<usize> {
-        self.min_presses_ilp(&self.voltages)
+    fn compute_sum_button_presses(&self) -> Option<usize> {
+        let a: Vec<Vec<i64>> = (0..self.nb_lights())
+            .map(|index| {
+                self.buttons
+                    .iter()
+                    .map(|&button| self.toggles(button, index) as i64)
+                    .collect()
+            })
+            .collect();
+        let b: Vec<i64> = self.voltages
+            .iter()
+            .map(|&v| v as i64)
+            .collect();
+        minimize_sum_ilp(&a, &b).map(|presses| presses.iter().sum::<i64>() as usize)
     }
 }
 
 impl FromStr for Machine {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let elems = s.split_ascii_whitespace().collect::<Vec<&str>>();
-        let wanted_binary = elems[0]
+        let mut fields = scan("[{}] {}", s)?;
+        let lights: String = fields.parse_next()?;
+        let rest: String = fields.parse_next()?;
+        // Byte offsets in `s`, to report errors at the part that caused them
+        let rest_start = lights.len() + 3;
+        let (buttons, voltages) = rest
+            .rsplit_once(' ')
+            .ok_or_else(|| ParseError::new(1, rest_start + 1, "expected buttons then voltages"))?;
+        let voltages_start = rest_start + buttons.len() + 1;
+
+        let nb_lights = lights.len();
+        let wanted_lights = lights
             .chars()
-            .filter_map(|c| {
-                match c {
-                    '#' => Some('1'),
-                    '.' => Some('0'),
-                    _ => None,
-                }
-            })
-            .collect::<String>();
-        let wanted_lights = usize::from_str_radix(&wanted_binary, 2).map_err(|_| ())?;
-        let stripped = elems[elems.len() - 1]
-            .strip_prefix('{')
-            .and_then(|s| s.strip_suffix('}'))
-            .unwrap_or(elems[elems.len() - 1]);
-        let voltages: Vec<usize> = stripped
-            .split(',')
-            .filter_map(|num_str| num_str.parse::<usize>().ok())
-            .collect();
-        let buttons = elems[1..elems.len() - 1]
-            .iter()
-            .map(|&s| {
-                let stripped = s
-                    .strip_prefix('(')
-                    .and_then(|s| s.strip_suffix(')'))
-                    .unwrap_or(s);
-                stripped
-                    .split(',')
-                    .filter_map(|num_str| num_str.parse::<u8>().ok())
-                    .collect::<Vec<u8>>()
+            .fold(0, |acc, c| (acc << 1) | usize::from(c == '#'));
+        let voltages: Vec<usize> = bracketed(voltages).map_err(|e| {
+            e.offset_columns(voltages_start)
+        })?;
+        if voltages.len() != nb_lights {
+            let message = format!("expected {} voltages, got {}", nb_lights, voltages.len());
+            return Err(ParseError::new(1, voltages_start + 1, message));
+        }
+        let mut button_start = rest_start;
+        let buttons = buttons
+            .split(' ')
+            .map(|button| {
+                let start = button_start;
+                button_start += button.len() + 1;
+                (start, button)
             })
-            .map(|toggles| {
-                let mut button_value: usize = 0;
-                let max_size = wanted_binary.len() as u8;
-                for &toggle in &toggles {
-                    button_value |= 1 << (max_size - 1 - toggle);
-                }
-                button_value
+            .filter(|(_, button)| !button.is_empty())
+            .map(|(start, button)| {
+                let toggles: Vec<usize> = bracketed(button).map_err(|e| e.offset_columns(start))?;
+                toggles.iter().try_fold(0, |button_value, &toggle| {
+                    if toggle >= nb_lights {
+                        let message = format!("button toggles light {} of {}", toggle, nb_lights);
+                        return Err(ParseError::new(1, start + 1, message));
+                    }
+                    Ok(button_value | (1 << (nb_lights - 1 - toggle)))
+                })
             })
-            .collect::<Vec<usize>>();
-        if wanted_binary.len() != voltages.len() {
-            return Err(());
-        }
+            .collect::<Result<Vec<usize>, ParseError>>()?;
+
         Ok(Machine {
             wanted_lights,
             buttons,
-            ans: vec![0; 16],
             voltages,
         })
     }
@@ -150,14 +112,7 @@ fn part1(input: &str) -> usize {
         .collect();
     machines
         .into_iter()
-        .filter_map(|mut machine| {
-            let presses = machine.compute_xor_button_presses();
-            if let Some(count) = presses {
-                Some(count)
-            } else {
-                None
-            }
-        })
+        .filter_map(|machine| machine.compute_xor_button_presses())
         .sum()
 }
 
@@ -168,14 +123,7 @@ fn part2(input: &str) -> usize {
         .collect();
     machines
         .into_iter()
-        .filter_map(|mut machine| {
-            let presses = machine.compute_sum_button_presses();
-            if let Some(count) = presses {
-                Some(count)
-            } else {
-                None
-            }
-        })
+        .filter_map(|machine| machine.compute_sum_button_presses())
         .sum()
 }
 
@@ -241,6 +189,16 @@ mod tests {
         assert_eq!(result, 12);
     }
 
+    #[test]
+    fn test_parse_error_columns() {
+        let error = Machine::from_str("[.#] (0) (1,x) {1,2}").err().unwrap();
+        assert_eq!(error.column, 13);
+        let error = Machine::from_str("[.#] (0) (2) {1,2}").err().unwrap();
+        assert_eq!(error.column, 10);
+        let error = Machine::from_str("[.#] (0) (1) {1,2,3}").err().unwrap();
+        assert_eq!(error.column, 14);
+    }
+
     #[test]
     fn test_part2_edge_case() {
         let input = r#"